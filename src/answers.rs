@@ -0,0 +1,98 @@
+//! Collecting the answers of all test cases for a single write.
+
+use std::fmt::{Display, Write as _};
+use std::io::{self, Write};
+
+/// Buffer for the answers of the test cases, one per line, written out at
+/// once by `print` or `write_to`.
+#[derive(Default)]
+pub struct Answers {
+    out: String,
+    cases: usize,
+    case_prefix: bool,
+}
+
+impl Answers {
+    pub fn new() -> Self {
+        Answers::default()
+    }
+
+    /// Start each answer with `Case #i: `, counting the cases from 1.
+    pub fn with_case_prefix(mut self, enable: bool) -> Self {
+        self.case_prefix = enable;
+        self
+    }
+
+    fn begin(&mut self) {
+        self.cases += 1;
+        if self.case_prefix {
+            write!(self.out, "Case #{}: ", self.cases).unwrap();
+        }
+    }
+
+    /// Add the answer of the next test case.
+    pub fn push<T: Display>(&mut self, ans: T) {
+        self.begin();
+        writeln!(self.out, "{}", ans).unwrap();
+    }
+
+    /// Add the answer of the next test case, consisting of the items of
+    /// `ans` separated by spaces.
+    pub fn push_seq<I>(&mut self, ans: I)
+    where
+        I: IntoIterator,
+        I::Item: Display,
+    {
+        self.begin();
+        for (i, x) in ans.into_iter().enumerate() {
+            if i > 0 {
+                self.out.push(' ');
+            }
+            write!(self.out, "{}", x).unwrap();
+        }
+        self.out.push('\n');
+    }
+
+    /// Number of answers pushed so far.
+    pub fn len(&self) -> usize {
+        self.cases
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cases == 0
+    }
+
+    /// The output collected so far.
+    pub fn as_str(&self) -> &str {
+        &self.out
+    }
+
+    /// Write all answers to `w` and flush it.
+    pub fn write_to<W: Write>(&self, mut w: W) -> io::Result<()> {
+        w.write_all(self.out.as_bytes())?;
+        w.flush()
+    }
+
+    /// Write all answers to stdout.
+    pub fn print(&self) {
+        let stdout = io::stdout();
+        self.write_to(stdout.lock()).expect("failed to write answers");
+    }
+}
+
+#[test]
+fn test_answers() {
+    let mut ans = Answers::new().with_case_prefix(true);
+    ans.push(42);
+    ans.push("IMPOSSIBLE");
+    ans.push_seq([1, 2, 3]);
+    assert_eq!(ans.len(), 3);
+    let mut out = vec![];
+    ans.write_to(&mut out).unwrap();
+    assert_eq!(out, b"Case #1: 42\nCase #2: IMPOSSIBLE\nCase #3: 1 2 3\n");
+
+    let mut ans = Answers::new();
+    ans.push_seq(Vec::<u32>::new());
+    ans.push(1.5);
+    assert_eq!(ans.as_str(), "\n1.5\n");
+}
@@ -0,0 +1,116 @@
+//! Synthetic inputs and throughput measurements for the tokenizer.
+//!
+//! ```rust
+//! use comp_input::bench::{generate, measure, Backend, Shape};
+//!
+//! let input = generate(Shape::Ints, 10_000, 1);
+//! let m = measure(&input, Shape::Ints, Backend::BufReader(1 << 16));
+//! assert_eq!(m.tokens, 10_000);
+//! println!("{:.1} MB/s", m.mb_per_sec());
+//! ```
+
+use std::io::{BufRead, BufReader};
+use std::time::{Duration, Instant};
+
+use crate::{FormattedRead, Rng};
+
+/// Kind of tokens in a generated input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shape {
+    /// Signed 64-bit integers.
+    Ints,
+    /// Floating point numbers with up to 6 decimals.
+    Floats,
+    /// Lowercase words of 1 to 10 letters.
+    Words,
+    /// Integers, floats and words in turn.
+    Mixed,
+}
+
+/// How the input is handed to the reader.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Backend {
+    /// Through a `BufReader` with the given capacity, like stdin.
+    BufReader(usize),
+    /// As a single slice holding the whole input.
+    Slurp,
+}
+
+/// Result of `measure`.
+#[derive(Clone, Copy, Debug)]
+pub struct Measurement {
+    pub tokens: usize,
+    pub bytes: usize,
+    pub elapsed: Duration,
+}
+
+impl Measurement {
+    pub fn mb_per_sec(&self) -> f64 {
+        self.bytes as f64 / 1e6 / self.elapsed.as_secs_f64().max(1e-9)
+    }
+}
+
+/// Generate `count` tokens of the given shape, ten per line. The same `seed`
+/// gives the same input.
+pub fn generate(shape: Shape, count: usize, seed: u64) -> Vec<u8> {
+    let mut rng = Rng::new(seed);
+    let mut out = Vec::with_capacity(count * 8);
+    for i in 0..count {
+        let kind = match shape {
+            Shape::Mixed => [Shape::Ints, Shape::Floats, Shape::Words][i % 3],
+            shape => shape,
+        };
+        let x = rng.next_u64();
+        match kind {
+            Shape::Ints => out.extend_from_slice((x as i64 >> (x % 48)).to_string().as_bytes()),
+            Shape::Floats => out.extend_from_slice(format!("{:.6}", (x % 2_000_000_000) as f64 / 1e3 - 1e6).as_bytes()),
+            _ => out.extend((0..x % 10 + 1).map(|k| b'a' + (x >> (5 * k) & 0x1f) as u8 % 26)),
+        }
+        out.push(if i % 10 == 9 { b'\n' } else { b' ' });
+    }
+    out
+}
+
+fn read_all<R: BufRead>(reader: &mut FormattedRead<R>, shape: Shape) -> usize {
+    let mut tokens = 0;
+    loop {
+        let kind = match shape {
+            Shape::Mixed => [Shape::Ints, Shape::Floats, Shape::Words][tokens % 3],
+            shape => shape,
+        };
+        let ok = match kind {
+            Shape::Ints => reader.read_word_opt::<i64>().map(|x| x.is_some()),
+            Shape::Floats => reader.read_word_opt::<f64>().map(|x| x.is_some()),
+            _ => reader.read_word_opt::<String>().map(|x| x.is_some()),
+        };
+        match ok {
+            Ok(true) => tokens += 1,
+            _ => return tokens,
+        }
+    }
+}
+
+/// Read all tokens of `input`, generated with `shape`, and time it.
+pub fn measure(input: &[u8], shape: Shape, backend: Backend) -> Measurement {
+    let start = Instant::now();
+    let tokens = match backend {
+        Backend::BufReader(capacity) => read_all(&mut FormattedRead::new(BufReader::with_capacity(capacity, input)), shape),
+        Backend::Slurp => read_all(&mut FormattedRead::new(input), shape),
+    };
+    Measurement {
+        tokens,
+        bytes: input.len(),
+        elapsed: start.elapsed(),
+    }
+}
+
+#[test]
+fn test_bench() {
+    for &shape in &[Shape::Ints, Shape::Floats, Shape::Words, Shape::Mixed] {
+        let input = generate(shape, 1000, 7);
+        assert_eq!(input, generate(shape, 1000, 7));
+        for &backend in &[Backend::BufReader(64), Backend::Slurp] {
+            assert_eq!(measure(&input, shape, backend).tokens, 1000);
+        }
+    }
+}
@@ -0,0 +1,20 @@
+//! `fetch_samples <url> [dir]`: download the sample tests of a Codeforces
+//! or AtCoder problem into `dir`, by default `tests/samples`.
+
+use std::process;
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.is_empty() || args.len() > 2 {
+        eprintln!("usage: fetch_samples <url> [dir]");
+        process::exit(2);
+    }
+    let dir = args.get(1).map_or("tests/samples", String::as_str);
+    match comp_input::fetch_samples(&args[0], dir) {
+        Ok(n) => println!("wrote {} samples to {}", n, dir),
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,68 @@
+//! Reading of fixed size little endian values, bypassing text parsing.
+
+use std::io::{self, BufRead, Read};
+
+use crate::FormattedRead;
+
+/// Types that can be read from their little endian binary representation.
+pub trait FromLeBytes : Sized {
+    /// Size of the representation in bytes, at most 16.
+    const SIZE: usize;
+    /// `src` has exactly `SIZE` bytes.
+    fn from_le_slice(src: &[u8]) -> Self;
+}
+
+macro_rules! from_le_bytes_impl {
+    ($($t:ty)*) => {
+        $(
+            impl FromLeBytes for $t {
+                const SIZE: usize = std::mem::size_of::<$t>();
+
+                #[inline]
+                fn from_le_slice(src: &[u8]) -> $t {
+                    let mut bytes = [0; std::mem::size_of::<$t>()];
+                    bytes.copy_from_slice(src);
+                    <$t>::from_le_bytes(bytes)
+                }
+            }
+        )*
+    }
+}
+from_le_bytes_impl! { u8 u16 u32 u64 u128 i8 i16 i32 i64 i128 f32 f64 }
+
+impl<R: BufRead> FormattedRead<R> {
+    /// Read a value from the next `T::SIZE` bytes of the input, without
+    /// skipping any whitespace.
+    pub fn read_raw<T: FromLeBytes>(&mut self) -> io::Result<T> {
+        let mut bytes = [0; 16];
+        self.r.read_exact(&mut bytes[..T::SIZE])?;
+        Ok(T::from_le_slice(&bytes[..T::SIZE]))
+    }
+}
+
+#[test]
+fn test_binary() {
+    let mut input = b"2 ".to_vec();
+    for x in &[7u32, 1 << 20] {
+        input.extend_from_slice(&x.to_le_bytes());
+    }
+    input.extend_from_slice(&(-3i16).to_le_bytes());
+    input.extend_from_slice(&1.5f64.to_le_bytes());
+    input.extend_from_slice(b"\nok\n");
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+
+    input! {
+        reader =>
+            n: usize,
+            binary {
+                a: [u32; n],
+                b: (i16, f64),
+            },
+            c: String,
+    }
+
+    assert_eq!(a, vec![7, 1 << 20]);
+    assert_eq!(b, (-3, 1.5));
+    assert_eq!(c, "ok");
+    assert!(reader.read_raw::<u8>().is_err());
+}
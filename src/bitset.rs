@@ -0,0 +1,122 @@
+//! Compact sets of booleans.
+
+use std::io::{self, BufRead};
+
+use crate::{FormattedRead, FromAscii};
+
+/// A fixed number of bits packed into `u64` words.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BitSet {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl BitSet {
+    /// `len` bits, all unset.
+    pub fn new(len: usize) -> Self {
+        BitSet {
+            words: vec![0; len.div_ceil(64)],
+            len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    pub fn get(&self, i: usize) -> bool {
+        assert!(i < self.len, "bit index out of range");
+        self.words[i / 64] >> (i % 64) & 1 == 1
+    }
+
+    #[inline]
+    pub fn set(&mut self, i: usize, value: bool) {
+        assert!(i < self.len, "bit index out of range");
+        let mask = 1 << (i % 64);
+        if value {
+            self.words[i / 64] |= mask;
+        } else {
+            self.words[i / 64] &= !mask;
+        }
+    }
+
+    /// Number of set bits.
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// The underlying words, bit `i` being bit `i % 64` of word `i / 64`.
+    /// Bits past `len` are unset.
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.len).map(move |i| self.get(i))
+    }
+
+    fn push_bit(&mut self, value: bool) {
+        if self.len.is_multiple_of(64) {
+            self.words.push(0);
+        }
+        self.len += 1;
+        self.set(self.len - 1, value);
+    }
+}
+
+impl FromAscii for BitSet {
+    /// A word of `0`s and `1`s.
+    fn from_ascii(src: &[u8]) -> Option<BitSet> {
+        let mut bits = BitSet::new(0);
+        for &c in src {
+            match c {
+                b'0' => bits.push_bit(false),
+                b'1' => bits.push_bit(true),
+                _ => return None,
+            }
+        }
+        Some(bits)
+    }
+}
+
+impl<R: BufRead> FormattedRead<R> {
+    /// Read `n` words that are each `0` or `1`.
+    pub fn read_bits(&mut self, n: usize) -> io::Result<BitSet> {
+        let mut bits = BitSet::new(n);
+        for i in 0..n {
+            let bit = self.read_word_with(|w| match w {
+                b"0" => Some(false),
+                b"1" => Some(true),
+                _ => None,
+            })?;
+            if bit {
+                bits.set(i, true);
+            }
+        }
+        Ok(bits)
+    }
+}
+
+#[test]
+fn test_bitset() {
+    let input = b"1 0 1 1\n0110100000000000000000000000000000000000000000000000000000000000001\n";
+    let mut reader = FormattedRead::new(io::Cursor::new(&input[..]));
+    input! {
+        reader =>
+            a: bits(4),
+            b: BitSet,
+    }
+    assert_eq!(a.iter().collect::<Vec<_>>(), vec![true, false, true, true]);
+    assert_eq!(a.words(), &[0b1101]);
+    assert_eq!(b.len(), 67);
+    assert_eq!(b.count_ones(), 4);
+    assert!(b.get(66) && b.get(1) && !b.get(0));
+
+    let mut reader = FormattedRead::new(io::Cursor::new(&b"0 2"[..]));
+    assert!(reader.read_bits(2).is_err());
+}
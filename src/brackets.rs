@@ -0,0 +1,72 @@
+//! Balanced bracket groups, such as parenthesized expressions.
+
+use std::io::{self, BufRead};
+
+use crate::{BufReadExt, FormattedRead};
+
+fn closing(open: u8) -> Option<u8> {
+    match open {
+        b'(' => Some(b')'),
+        b'[' => Some(b']'),
+        b'{' => Some(b'}'),
+        _ => None,
+    }
+}
+
+impl<R: BufRead> FormattedRead<R> {
+    /// Read a group starting with `(`, `[` or `{` up to its matching
+    /// closing bracket, as one token. The group may contain whitespace and
+    /// line breaks, and is returned verbatim including its brackets for a
+    /// parser of its own. Fails with `InvalidData` if the brackets are not
+    /// properly nested.
+    pub fn read_bracketed(&mut self) -> io::Result<Vec<u8>> {
+        self.skip_delims()?;
+        let first = self.r.fill_buf_nonempty()?[0];
+        if closing(first).is_none() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "expected an opening bracket"));
+        }
+        let mut res = vec![];
+        let mut open = vec![];
+        loop {
+            let buf = self.r.fill_buf_nonempty()?;
+            let mut end = None;
+            for (i, &c) in buf.iter().enumerate() {
+                if let Some(close) = closing(c) {
+                    open.push(close);
+                } else if c == b')' || c == b']' || c == b'}' {
+                    if open.pop() != Some(c) {
+                        return Err(io::Error::new(io::ErrorKind::InvalidData, "mismatched bracket"));
+                    }
+                    if open.is_empty() {
+                        end = Some(i+1);
+                        break;
+                    }
+                }
+            }
+            let l = end.unwrap_or(buf.len());
+            res.extend_from_slice(&buf[..l]);
+            self.r.consume(l);
+            if end.is_some() {
+                break;
+            }
+        }
+        self.end_token(None);
+        self.count(Ok(res))
+    }
+}
+
+#[test]
+fn test_bracketed() {
+    let input = b"3 (1 + [2 *\n {3}]) [] x (()";
+    let mut reader = FormattedRead::new(std::io::BufReader::with_capacity(4, &input[..]));
+    input! { reader => n: u32, e: bracketed, f: bracketed }
+    assert_eq!(n, 3);
+    assert_eq!(e, "(1 + [2 *\n {3}])");
+    assert_eq!(f, "[]");
+    assert!(reader.read_bracketed().is_err());
+    reader.read_word::<char>().unwrap();
+    assert_eq!(reader.read_bracketed().unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+
+    let mut reader = FormattedRead::new(&b"(]"[..]);
+    assert_eq!(reader.read_bracketed().unwrap_err().kind(), io::ErrorKind::InvalidData);
+}
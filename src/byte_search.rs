@@ -0,0 +1,21 @@
+//! Plain versions of the `memchr` functions, used instead of the `memchr`
+//! crate by the `bundle` feature or without the `memchr` feature, so that
+//! the crate can be inlined into a single source file.
+
+#[inline]
+pub(crate) fn memchr(needle: u8, haystack: &[u8]) -> Option<usize> {
+    haystack.iter().position(|&b| b == needle)
+}
+
+#[inline]
+pub(crate) fn memchr2(needle1: u8, needle2: u8, haystack: &[u8]) -> Option<usize> {
+    haystack.iter().position(|&b| b == needle1 || b == needle2)
+}
+
+#[test]
+fn test_byte_search() {
+    assert_eq!(memchr(b'\n', b"ab\ncd\n"), Some(2));
+    assert_eq!(memchr(b'\n', b"abc"), None);
+    assert_eq!(memchr2(b'\n', b'\r', b"ab\r\n"), Some(2));
+    assert_eq!(memchr2(b'\n', b'\r', b""), None);
+}
@@ -0,0 +1,120 @@
+//! Recording the raw input of each test case, to report the case that
+//! made the solution panic.
+
+use std::io::{self, BufRead, Write};
+use std::sync::{Arc, Mutex};
+
+use crate::FormattedRead;
+
+#[derive(Default)]
+pub(crate) struct CaseLog {
+    case: usize,
+    active: bool,
+    bytes: Vec<u8>,
+}
+
+impl CaseLog {
+    pub(crate) fn record(&mut self, bytes: &[u8]) {
+        if self.active {
+            self.bytes.extend_from_slice(bytes);
+        }
+    }
+
+    /// Drop the last `n` bytes recorded, which were given back.
+    pub(crate) fn unrecord(&mut self, n: usize) {
+        let len = self.bytes.len().saturating_sub(n);
+        self.bytes.truncate(len);
+    }
+}
+
+impl<R: BufRead> FormattedRead<R> {
+    /// Keep the bytes consumed between `begin_case` and `end_case`, so that
+    /// the input of the case being solved can be shown when it fails.
+    pub fn with_case_capture(mut self) -> Self {
+        self.r.capture = Some(Arc::new(Mutex::new(CaseLog::default())));
+        self
+    }
+
+    /// Start recording the input of the next test case. Cases are numbered
+    /// from 1.
+    pub fn begin_case(&mut self) {
+        if let Some(capture) = &self.r.capture {
+            let mut log = capture.lock().unwrap();
+            log.case += 1;
+            log.active = true;
+            log.bytes.clear();
+        }
+    }
+
+    /// Stop recording and discard the input of the current test case.
+    pub fn end_case(&mut self) {
+        if let Some(capture) = &self.r.capture {
+            let mut log = capture.lock().unwrap();
+            log.active = false;
+            log.bytes.clear();
+        }
+    }
+
+    /// The number and the input read so far of the current test case, if
+    /// case capture is enabled and a case is active.
+    pub fn current_case(&self) -> Option<(usize, Vec<u8>)> {
+        let log = self.r.capture.as_ref()?.lock().unwrap();
+        if log.active {
+            Some((log.case, log.bytes.clone()))
+        } else {
+            None
+        }
+    }
+
+    /// Install a panic hook printing the input of the current test case to
+    /// stderr before the previous hook runs. Requires `with_case_capture`.
+    pub fn install_case_dump_hook(&self) {
+        let capture = match &self.r.capture {
+            Some(capture) => capture.clone(),
+            None => return,
+        };
+        let prev = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if let Ok(log) = capture.try_lock() {
+                if log.active {
+                    let stderr = io::stderr();
+                    let mut stderr = stderr.lock();
+                    let _ = writeln!(stderr, "input of case #{}:", log.case);
+                    let _ = stderr.write_all(&log.bytes);
+                    let _ = writeln!(stderr);
+                }
+            }
+            prev(info);
+        }));
+    }
+}
+
+#[test]
+fn test_case_capture() {
+    let input = b"2\n3\n1 2 3\n2\n4 x\n";
+    let mut reader = FormattedRead::new(io::Cursor::new(&input[..])).with_case_capture();
+    input! { reader => t: usize }
+    let mut failed = None;
+    for _ in 0..t {
+        reader.begin_case();
+        input! { reader => n: usize }
+        if (0..n).any(|_| reader.read_word::<u32>().is_err()) {
+            failed = reader.current_case();
+            break;
+        }
+        reader.end_case();
+    }
+    let (case, bytes) = failed.unwrap();
+    assert_eq!(case, 2);
+    assert_eq!(&bytes[..], b"2\n4 ");
+
+    // a word spanning buffers that failed to parse is captured once when
+    // it is read again
+    let input = b"1\nabcdef 7\n";
+    let mut reader = FormattedRead::new(io::BufReader::with_capacity(2, &input[..])).with_case_capture();
+    input! { reader => _t: usize }
+    reader.begin_case();
+    assert!(reader.read_word::<u32>().is_err());
+    assert_eq!(reader.read_word::<String>().unwrap(), "abcdef");
+    assert_eq!(reader.current_case().unwrap().1, b"abcdef ");
+}
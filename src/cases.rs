@@ -0,0 +1,65 @@
+//! Splitting multi-case inputs into one reader per test case.
+
+use std::io::{self, BufRead, Cursor};
+
+use crate::FormattedRead;
+
+/// Reader over the words of a single test case, see `split_cases`.
+pub type CaseReader = FormattedRead<Cursor<Vec<u8>>>;
+
+impl<R: BufRead> FormattedRead<R> {
+    /// Read `t` test cases up front. For each case `header` reads the case
+    /// header and returns it together with the number of words that make
+    /// up the rest of the case. These words are moved into a separate
+    /// reader, so that the cases can be solved independently (e.g. in
+    /// parallel) and a malformed case cannot affect the following ones.
+    /// They are read with the settings of this reader, see `config`.
+    pub fn split_cases<H, F>(&mut self, t: usize, mut header: F) -> io::Result<Vec<(H, CaseReader)>>
+    where
+        F: FnMut(&mut Self) -> io::Result<(H, usize)>,
+    {
+        let sep = if self.delims.contains(b'\n') {
+            b'\n'
+        } else {
+            (0..=255).find(|&c| self.delims.contains(c)).unwrap_or(b' ')
+        };
+        let mut cases = Vec::with_capacity(crate::checked_len::<(H, CaseReader), _>(self.max_alloc(), t)?);
+        for _ in 0..t {
+            let (h, words) = header(self)?;
+            let mut bytes = vec![];
+            for _ in 0..words {
+                self.read_word_with(|w| {
+                    bytes.extend_from_slice(w);
+                    Some(())
+                })?;
+                bytes.push(sep);
+            }
+            cases.push((h, FormattedRead::new(Cursor::new(bytes)).with_config(self.config())));
+        }
+        Ok(cases)
+    }
+}
+
+#[test]
+fn test_split_cases() {
+    let input = b"3\n2 1 2\n1\n 7\n0\n";
+    let mut reader = FormattedRead::new(Cursor::new(&input[..]));
+    input! { reader => t: usize }
+    let cases = reader.split_cases(t, |r| {
+        let n = r.read_word::<usize>()?;
+        Ok((n, n))
+    }).unwrap();
+    let sums: Vec<u32> = cases.into_iter().map(|(n, mut case)| {
+        input! { case => a: [u32; n] }
+        assert!(!case.has_next().unwrap());
+        a.iter().sum()
+    }).collect();
+    assert_eq!(sums, vec![3, 7, 0]);
+
+    // the cases are read with the same settings
+    let mut reader = FormattedRead::new(Cursor::new(&b"2 1,5 2,25"[..])).with_decimal_comma(true).with_max_alloc(1000);
+    let mut cases = reader.split_cases(1, |r| Ok(((), r.read_word::<usize>()?))).unwrap();
+    let case = &mut cases[0].1;
+    assert_eq!(case.read_word::<f64>().unwrap(), 1.5);
+    assert_eq!(case.max_alloc(), 1000);
+}
@@ -0,0 +1,209 @@
+//! Reading directly into map, set and other collection types.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::hash::Hash;
+use std::iter::FromIterator;
+use std::io::{self, BufRead};
+
+use crate::{FormattedRead, FromAscii};
+
+/// What to do when reading an element into a set that already contains it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Duplicates {
+    /// Keep the set as it is.
+    Ignore,
+    /// Fail with `InvalidData`.
+    Reject,
+}
+
+fn insert_checked(inserted: bool, dup: Duplicates) -> io::Result<()> {
+    if inserted || dup == Duplicates::Ignore {
+        Ok(())
+    } else {
+        Err(io::Error::new(io::ErrorKind::InvalidData, "duplicate set element"))
+    }
+}
+
+/// Values read by `read_compressed` together with their coordinate
+/// compression.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Compressed<T> {
+    /// The values in input order.
+    pub values: Vec<T>,
+    /// The index of each value in `sorted`.
+    pub ranks: Vec<usize>,
+    /// The distinct values in increasing order.
+    pub sorted: Vec<T>,
+}
+
+/// Expand `(value, count)` runs into the full sequence, failing with
+/// `InvalidData` if it would be longer than `max_len`.
+pub fn expand_runs<T: Clone>(runs: &[(T, usize)], max_len: usize) -> io::Result<Vec<T>> {
+    let total = runs.iter().try_fold(0usize, |acc, &(_, c)| acc.checked_add(c).filter(|&t| t <= max_len));
+    let total = total.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "run-length expansion too long"))?;
+    let mut res = Vec::with_capacity(total);
+    for (x, c) in runs {
+        res.extend(std::iter::repeat_n(x, *c).cloned());
+    }
+    Ok(res)
+}
+
+impl<R: BufRead> FormattedRead<R> {
+    /// Read `n` words into any collection, e.g. a `VecDeque<T>` or `BinaryHeap<T>`.
+    pub fn read_collect<C: FromIterator<T>, T: FromAscii>(&mut self, n: usize) -> io::Result<C> {
+        (0..n).map(|_| self.read_word()).collect()
+    }
+
+    /// Read `n` key/value pairs. Later values replace earlier ones with the
+    /// same key.
+    pub fn read_map<K: FromAscii + Eq + Hash, V: FromAscii>(&mut self, n: usize) -> io::Result<HashMap<K, V>> {
+        (0..n).map(|_| Ok((self.read_word()?, self.read_word()?))).collect()
+    }
+
+    /// Like `read_map`, but ordered by key.
+    pub fn read_btree_map<K: FromAscii + Ord, V: FromAscii>(&mut self, n: usize) -> io::Result<BTreeMap<K, V>> {
+        (0..n).map(|_| Ok((self.read_word()?, self.read_word()?))).collect()
+    }
+
+    /// Read `n` words and compute the rank of each among the distinct
+    /// values.
+    pub fn read_compressed<T: FromAscii + Ord + Clone>(&mut self, n: usize) -> io::Result<Compressed<T>> {
+        let values: Vec<T> = self.read_collect(n)?;
+        let mut sorted = values.clone();
+        sorted.sort_unstable();
+        sorted.dedup();
+        let ranks = values.iter().map(|x| sorted.binary_search(x).unwrap()).collect();
+        Ok(Compressed { values, ranks, sorted })
+    }
+
+    /// Read `n` words and sort them together with their 0-based positions
+    /// in the input. Equal values stay in input order.
+    pub fn read_sorted_with_idx<T: FromAscii + Ord>(&mut self, n: usize) -> io::Result<Vec<(T, usize)>> {
+        let mut res = (0..n).map(|i| Ok((self.read_word()?, i))).collect::<io::Result<Vec<_>>>()?;
+        res.sort_unstable();
+        Ok(res)
+    }
+
+    /// Read `n` words into a set.
+    pub fn read_set<T: FromAscii + Eq + Hash>(&mut self, n: usize, dup: Duplicates) -> io::Result<HashSet<T>> {
+        let mut set = HashSet::with_capacity(crate::checked_len::<T, _>(self.max_alloc(), n)?);
+        for _ in 0..n {
+            insert_checked(set.insert(self.read_word()?), dup)?;
+        }
+        Ok(set)
+    }
+
+    /// Like `read_set`, but ordered.
+    pub fn read_btree_set<T: FromAscii + Ord>(&mut self, n: usize, dup: Duplicates) -> io::Result<BTreeSet<T>> {
+        let mut set = BTreeSet::new();
+        for _ in 0..crate::checked_len::<T, _>(self.max_alloc(), n)? {
+            insert_checked(set.insert(self.read_word()?), dup)?;
+        }
+        Ok(set)
+    }
+}
+
+#[test]
+fn test_map() {
+    let input = b"3\nalice 10\nbob -2\nalice 7\n2\n2 x\n1 y\n";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+
+    input! {
+        reader =>
+            n: usize,
+            scores: map(String => i64; n),
+            m: usize,
+            names: btreemap(usize1 => char; m),
+    }
+
+    assert_eq!(scores.len(), 2);
+    assert_eq!(scores["alice"], 7);
+    assert_eq!(scores["bob"], -2);
+    assert_eq!(names.into_iter().collect::<Vec<_>>(), vec![(0, 'y'), (1, 'x')]);
+
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&b"a 1 b 2"[..]));
+    let m = reader.read_btree_map::<String, u32>(2).unwrap();
+    assert_eq!(m["b"], 2);
+}
+
+#[test]
+fn test_set() {
+    let input = b"5\n3 1 3 2 1\n3\n2 3 1\n";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+
+    input! {
+        reader =>
+            n: usize,
+            a: btreeset(u32; n),
+            m: usize,
+            b: set(usize1; m, distinct),
+    }
+
+    assert_eq!(a.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+    assert_eq!(b, (0..3).collect());
+
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&b"1 2 1"[..]));
+    assert!(reader.read_set::<u8>(3, Duplicates::Reject).is_err());
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&b"1 2 1"[..]));
+    assert_eq!(reader.read_set::<u8>(3, Duplicates::Ignore).unwrap().len(), 2);
+}
+
+#[test]
+fn test_collect() {
+    use std::collections::{BinaryHeap, VecDeque};
+
+    let input = b"3\n5 -1 2\n4 1 3\n1 2 3 4 5 6\n";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+
+    input! {
+        reader =>
+            n: usize,
+            a: [i64; n] as VecDeque,
+            b: [(u8, usize1); n / 2] as BinaryHeap<_>,
+            c: [u32; 1] as BinaryHeap<_>,
+    }
+
+    assert_eq!(a, VecDeque::from(vec![5, -1, 2]));
+    assert_eq!(b.into_sorted_vec(), vec![(4, 0)]);
+    assert_eq!(c.peek(), Some(&3));
+
+    let d: BTreeSet<u8> = reader.read_collect(6).unwrap();
+    assert_eq!(d.len(), 6);
+}
+
+#[test]
+fn test_rle() {
+    let input = b"a 2 b 0 c 3\n2 5 1 7 2";
+    let mut reader = FormattedRead::new(io::Cursor::new(&input[..]));
+    input! {
+        reader =>
+            runs: rle(char; 3),
+            k: usize,
+            seq: rle(u32; k; 10),
+    }
+    assert_eq!(runs, vec![('a', 2), ('b', 0), ('c', 3)]);
+    assert_eq!(seq, vec![5, 7, 7]);
+    assert_eq!(expand_runs(&runs, 5).unwrap(), vec!['a', 'a', 'c', 'c', 'c']);
+    assert!(expand_runs(&runs, 4).is_err());
+    assert!(expand_runs(&[(0, usize::MAX), (1, 1)], usize::MAX).is_err());
+}
+
+#[test]
+fn test_compressed() {
+    let mut reader = FormattedRead::new(io::Cursor::new(&b"5 100 -3 100 7 -3"[..]));
+    input! {
+        reader =>
+            n: usize,
+            c: compressed(i64; n),
+    }
+    assert_eq!(c.values, vec![100, -3, 100, 7, -3]);
+    assert_eq!(c.ranks, vec![2, 0, 2, 1, 0]);
+    assert_eq!(c.sorted, vec![-3, 7, 100]);
+}
+
+#[test]
+fn test_sorted_with_idx() {
+    let mut reader = FormattedRead::new(io::Cursor::new(&b"30 10 20 10"[..]));
+    input! { reader => a: sorted_with_idx(u32; 4) }
+    assert_eq!(a, vec![(10, 1), (10, 3), (20, 2), (30, 0)]);
+}
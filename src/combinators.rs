@@ -0,0 +1,157 @@
+//! Small parser combinators for formats that are not a sequence of
+//! whitespace separated words, such as S-expressions.
+//!
+//! A parser is a function `FnMut(&mut FormattedRead<R>) -> io::Result<T>`.
+//! Like Parsec, `many` and `sep_by` stop at a parser that fails without
+//! consuming input, and pass on the error of one that fails after
+//! consuming some. Whitespace in front of a token is skipped.
+
+use std::io::{self, BufRead};
+
+use crate::{consume_while, FormattedRead};
+
+impl<R: BufRead> FormattedRead<R> {
+    fn skip_space(&mut self) -> io::Result<()> {
+        let delims = &self.delims;
+        match consume_while(&mut self.r, |c| delims.contains(c)) {
+            Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof => Ok(()),
+            res => res,
+        }
+    }
+
+    /// Consume `lit` if the input continues with it, returning whether it
+    /// did. The input is left unchanged otherwise.
+    pub fn try_token(&mut self, lit: &[u8]) -> io::Result<bool> {
+        self.skip_space()?;
+        let mut matched = 0;
+        while matched < lit.len() {
+            let buf = self.r.fill_buf()?;
+            let n = (lit.len() - matched).min(buf.len());
+            if n == 0 || buf[..n] != lit[matched..matched+n] {
+                self.r.unread(&lit[..matched]);
+                return Ok(false);
+            }
+            self.r.consume(n);
+            matched += n;
+        }
+        self.end_token(None);
+        Ok(true)
+    }
+
+    /// Consume `lit`, failing with `InvalidData` if the input does not
+    /// continue with it. Unlike a word, `lit` does not need to be followed
+    /// by whitespace.
+    pub fn token(&mut self, lit: &[u8]) -> io::Result<()> {
+        if self.try_token(lit)? {
+            Ok(())
+        } else {
+            let msg = format!("expected `{}`", String::from_utf8_lossy(lit));
+            Err(io::Error::new(io::ErrorKind::InvalidData, msg))
+        }
+    }
+
+    /// Read the longest nonempty run of bytes satisfying `pred`, e.g. a
+    /// number or a name directly followed by a bracket.
+    pub fn take_while<P: FnMut(u8) -> bool>(&mut self, mut pred: P) -> io::Result<Vec<u8>> {
+        self.skip_space()?;
+        let mut res = vec![];
+        loop {
+            let buf = self.r.fill_buf()?;
+            let l = buf.iter().position(|&c| !pred(c)).unwrap_or(buf.len());
+            let done = l < buf.len() || buf.is_empty();
+            res.extend_from_slice(&buf[..l]);
+            self.r.consume(l);
+            if done {
+                break;
+            }
+        }
+        if res.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "expected a token"));
+        }
+        self.end_token(None);
+        Ok(res)
+    }
+
+    /// Run `f` as often as it succeeds, see the module documentation for
+    /// when it stops.
+    pub fn many<T, F>(&mut self, mut f: F) -> io::Result<Vec<T>>
+    where
+        F: FnMut(&mut Self) -> io::Result<T>,
+    {
+        let mut res = vec![];
+        loop {
+            self.skip_space()?;
+            let start = self.bytes_consumed();
+            match f(self) {
+                Ok(x) => res.push(x),
+                Err(_) if self.bytes_consumed() == start => return Ok(res),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Zero or more items read by `f`, separated by `sep`, e.g. `1, 2, 3`.
+    pub fn sep_by<T, F>(&mut self, sep: &[u8], mut f: F) -> io::Result<Vec<T>>
+    where
+        F: FnMut(&mut Self) -> io::Result<T>,
+    {
+        self.skip_space()?;
+        let start = self.bytes_consumed();
+        let mut res = match f(self) {
+            Ok(x) => vec![x],
+            Err(_) if self.bytes_consumed() == start => return Ok(vec![]),
+            Err(e) => return Err(e),
+        };
+        while self.try_token(sep)? {
+            res.push(f(self)?);
+        }
+        Ok(res)
+    }
+
+    /// Read `open`, then a value using `f`, then `close`, e.g. a list in
+    /// brackets with `delimited(b"[", b"]", |r| r.sep_by(b",", item))`.
+    pub fn delimited<T, F>(&mut self, open: &[u8], close: &[u8], f: F) -> io::Result<T>
+    where
+        F: FnOnce(&mut Self) -> io::Result<T>,
+    {
+        self.token(open)?;
+        let res = f(self)?;
+        self.token(close)?;
+        Ok(res)
+    }
+}
+
+#[test]
+fn test_sexp() {
+    #[derive(Debug, PartialEq)]
+    enum Sexp {
+        Atom(String),
+        List(Vec<Sexp>),
+    }
+
+    fn sexp<R: BufRead>(r: &mut FormattedRead<R>) -> io::Result<Sexp> {
+        if r.try_token(b"(")? {
+            let items = r.many(sexp)?;
+            r.token(b")")?;
+            Ok(Sexp::List(items))
+        } else {
+            let atom = r.take_while(|c| c.is_ascii_alphanumeric())?;
+            Ok(Sexp::Atom(String::from_utf8(atom).unwrap()))
+        }
+    }
+
+    let input = b"(add 1\n (mul x 3)) [1, 2,3] []";
+    let mut reader = FormattedRead::new(std::io::BufReader::with_capacity(3, &input[..]));
+    let atom = |s: &str| Sexp::Atom(s.to_string());
+    assert_eq!(
+        sexp(&mut reader).unwrap(),
+        Sexp::List(vec![atom("add"), atom("1"), Sexp::List(vec![atom("mul"), atom("x"), atom("3")])]),
+    );
+    let number = |r: &mut FormattedRead<_>| {
+        let digits = r.take_while(|c| c.is_ascii_digit())?;
+        Ok(std::str::from_utf8(&digits).unwrap().parse::<u32>().unwrap())
+    };
+    assert_eq!(reader.delimited(b"[", b"]", |r| r.sep_by(b",", number)).unwrap(), vec![1, 2, 3]);
+    assert_eq!(reader.delimited(b"[", b"]", |r| r.sep_by(b",", number)).unwrap(), Vec::<u32>::new());
+    assert!(reader.token(b"]").is_err());
+}
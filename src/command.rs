@@ -0,0 +1,94 @@
+//! Reading the output of another program, e.g. a test generator.
+
+use std::io::{self, BufRead, BufReader, Read};
+use std::process::{Child, ChildStdout, Command, Stdio};
+
+use crate::FormattedRead;
+
+/// The standard output of a child process, see
+/// `FormattedRead::from_command`. At the end of the output the child is
+/// waited for, and an unsuccessful exit status is reported as an error.
+/// Dropping the source kills a child that is still running.
+pub struct CommandSource {
+    child: Child,
+    out: BufReader<ChildStdout>,
+    exited: bool,
+}
+
+impl CommandSource {
+    fn check_exit(&mut self) -> io::Result<()> {
+        if !self.exited {
+            self.exited = true;
+            let status = self.child.wait()?;
+            if !status.success() {
+                return Err(io::Error::other(format!("command failed with {}", status)));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Read for CommandSource {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.out.read(buf)?;
+        if n == 0 && !buf.is_empty() {
+            self.check_exit()?;
+        }
+        Ok(n)
+    }
+}
+
+impl BufRead for CommandSource {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.out.fill_buf()?.is_empty() {
+            self.check_exit()?;
+        }
+        self.out.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.out.consume(amt);
+    }
+}
+
+impl Drop for CommandSource {
+    fn drop(&mut self) {
+        if !self.exited {
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+        }
+    }
+}
+
+impl FormattedRead<CommandSource> {
+    /// Spawn `cmd` with its standard output piped into the returned
+    /// reader. Its standard input is empty.
+    pub fn from_command(mut cmd: Command) -> io::Result<Self> {
+        let mut child = cmd.stdin(Stdio::null()).stdout(Stdio::piped()).spawn()?;
+        let out = BufReader::new(child.stdout.take().expect("stdout is piped"));
+        Ok(FormattedRead::new(CommandSource { child, out, exited: false }))
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn test_from_command() {
+    let sh = |script: &str| {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(script);
+        FormattedRead::from_command(cmd).unwrap()
+    };
+
+    let mut reader = sh("echo 3; echo 1 2 3");
+    input! { reader => n: usize, a: [u32; n] }
+    assert_eq!(a, vec![1, 2, 3]);
+    assert!(!reader.has_next().unwrap());
+
+    let mut reader = sh("echo 5; exit 2");
+    assert_eq!(reader.read_word::<u32>().unwrap(), 5);
+    assert!(reader.has_next().is_err());
+
+    // dropping the reader stops a generator that would run forever
+    let mut reader = sh("yes 1");
+    assert_eq!(reader.read_word::<u8>().unwrap(), 1);
+}
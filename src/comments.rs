@@ -0,0 +1,83 @@
+//! Source adapter removing comments from the input.
+
+use std::io::{self, BufRead, Read};
+
+/// Wraps a reader, removing everything from a comment marker up to (but not
+/// including) the end of the line, so annotated inputs parse like clean ones.
+pub struct SkipComments<R: BufRead> {
+    r: R,
+    marker: u8,
+    in_comment: bool,
+    buf: Vec<u8>,
+    pos: usize,
+}
+
+impl<R: BufRead> SkipComments<R> {
+    pub fn new(r: R, marker: u8) -> Self {
+        SkipComments {
+            r,
+            marker,
+            in_comment: false,
+            buf: vec![],
+            pos: 0,
+        }
+    }
+}
+
+impl<R: BufRead> Read for SkipComments<R> {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        let buf = self.fill_buf()?;
+        let n = buf.len().min(out.len());
+        out[..n].copy_from_slice(&buf[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for SkipComments<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        while self.pos == self.buf.len() {
+            self.buf.clear();
+            self.pos = 0;
+            let chunk = self.r.fill_buf()?;
+            if chunk.is_empty() {
+                break;
+            }
+            for &c in chunk {
+                if self.in_comment {
+                    if c == b'\n' {
+                        self.in_comment = false;
+                        self.buf.push(c);
+                    }
+                } else if c == self.marker {
+                    self.in_comment = true;
+                } else {
+                    self.buf.push(c);
+                }
+            }
+            let l = chunk.len();
+            self.r.consume(l);
+        }
+        Ok(&self.buf[self.pos..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos += amt;
+    }
+}
+
+#[test]
+fn test_skip_comments() {
+    let input = b"# two numbers\n3 # first\n4\n# a line\n#\nword#suffix\n";
+    let mut reader = crate::FormattedRead::new(SkipComments::new(&input[..], b'#'));
+
+    input! {
+        reader =>
+            a, b: u32,
+            c: String,
+    }
+
+    assert_eq!((a, b), (3, 4));
+    assert_eq!(c, "word");
+    assert!(reader.read_word::<String>().is_err());
+}
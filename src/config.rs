@@ -0,0 +1,128 @@
+//! Detecting whether the solution runs locally or on a judge.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use crate::FormattedRead;
+
+/// Environment variable that makes local runs of `FormattedRead::auto`
+/// echo the input they read to stderr, if set to a non-empty value.
+pub const ECHO_ENV_VAR: &str = "COMP_INPUT_ECHO";
+
+/// File read by local runs of `FormattedRead::auto` if there is no
+/// command line argument.
+const LOCAL_INPUT_FILE: &str = "input.txt";
+
+/// Environment variable overriding `is_local`: `0`, `false` or an empty
+/// value mean a judge run, anything else a local one.
+pub const LOCAL_ENV_VAR: &str = "COMP_INPUT_LOCAL";
+
+/// Whether the program runs on the contestant's machine rather than on the
+/// judge. Decided by `COMP_INPUT_LOCAL` if it is set, otherwise local runs
+/// are the ones built with debug assertions. Local-only conveniences such
+/// as reading input files or printing statistics should check this, so
+/// that the submitted code stays unchanged.
+pub fn is_local() -> bool {
+    static LOCAL: OnceLock<bool> = OnceLock::new();
+    *LOCAL.get_or_init(|| parse_local(std::env::var(LOCAL_ENV_VAR).ok().as_deref()))
+}
+
+fn parse_local(var: Option<&str>) -> bool {
+    match var {
+        Some(v) => !matches!(v.trim(), "" | "0" | "false"),
+        None => cfg!(debug_assertions),
+    }
+}
+
+/// The file a local run reads: the first command line argument, or
+/// `input.txt` if it exists.
+fn local_input_path(arg: Option<String>) -> Option<PathBuf> {
+    arg.map(PathBuf::from).or_else(|| {
+        let default = PathBuf::from(LOCAL_INPUT_FILE);
+        if default.is_file() { Some(default) } else { None }
+    })
+}
+
+/// Source copying the input it passes on to stderr.
+struct Echo<R>(R);
+
+impl<R: BufRead> Read for Echo<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.0.read(buf)?;
+        let _ = io::stderr().write_all(&buf[..n]);
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for Echo<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.0.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        if let Ok(buf) = self.0.fill_buf() {
+            let _ = io::stderr().write_all(&buf[..amt.min(buf.len())]);
+        }
+        self.0.consume(amt);
+    }
+}
+
+impl FormattedRead<Box<dyn BufRead>> {
+    /// A reader that behaves differently locally and on the judge, see
+    /// `is_local`. On the judge it reads stdin. Locally it reads the file
+    /// named by the first command line argument or `input.txt` if either
+    /// is there, and echoes the input to stderr if `COMP_INPUT_ECHO` is
+    /// set.
+    pub fn auto() -> io::Result<Self> {
+        let stdin = || -> Box<dyn BufRead> { Box::new(io::stdin().lock()) };
+        if !is_local() {
+            return Ok(FormattedRead::new(stdin()));
+        }
+        let source = match local_input_path(std::env::args().nth(1)) {
+            Some(path) => Box::new(BufReader::new(File::open(path)?)),
+            None => stdin(),
+        };
+        let echo = std::env::var_os(ECHO_ENV_VAR).is_some_and(|v| !v.is_empty());
+        Ok(FormattedRead::new(if echo {
+            Box::new(Echo(source))
+        } else {
+            source
+        }))
+    }
+}
+
+impl<R: BufRead> FormattedRead<R> {
+    /// In local runs, print the number of tokens and bytes read so far to
+    /// stderr. Does nothing on the judge.
+    pub fn print_stats(&self) {
+        if is_local() {
+            eprintln!("{}", self.stats());
+        }
+    }
+
+    fn stats(&self) -> String {
+        format!("read {} tokens, {} bytes", self.tokens_read(), self.bytes_consumed())
+    }
+}
+
+#[test]
+fn test_parse_local() {
+    assert!(parse_local(Some("1")));
+    assert!(!parse_local(Some("0")));
+    assert!(!parse_local(Some("false")));
+    assert!(!parse_local(Some("")));
+    assert_eq!(parse_local(None), cfg!(debug_assertions));
+}
+
+#[test]
+fn test_auto_source() {
+    assert_eq!(local_input_path(Some("in/1.txt".to_string())), Some(PathBuf::from("in/1.txt")));
+    // the tests run in the package root, which has no input.txt
+    assert_eq!(local_input_path(None), None);
+
+    let mut reader = FormattedRead::new(&b"3 10 20 30\n"[..]);
+    input! { reader => n: usize, _a: [u32; n] }
+    assert_eq!(reader.stats(), "read 4 tokens, 11 bytes");
+}
@@ -0,0 +1,129 @@
+//! Source wrapper keeping track of the consumed bytes.
+
+use std::io::{self, BufRead, Read};
+use std::sync::{Arc, Mutex};
+
+use crate::capture::CaseLog;
+use crate::trace::Trace;
+
+pub(crate) struct Counted<R> {
+    pub(crate) inner: R,
+    pub(crate) consumed: u64,
+    pub(crate) capture: Option<Arc<Mutex<CaseLog>>>,
+    pub(crate) trace: Arc<Trace>,
+    /// Whether a UTF-8 byte order mark at the start is still to be skipped.
+    pub(crate) skip_bom: bool,
+    /// Bytes given back with `unread`, returned before those of `inner`.
+    unread: Vec<u8>,
+    unread_pos: usize,
+}
+
+const BOM: &[u8] = b"\xef\xbb\xbf";
+
+impl<R> Counted<R> {
+    pub(crate) fn new(inner: R) -> Self {
+        Counted {
+            inner,
+            consumed: 0,
+            capture: None,
+            trace: Arc::default(),
+            skip_bom: true,
+            unread: vec![],
+            unread_pos: 0,
+        }
+    }
+
+    /// Put `bytes`, which were just consumed, back in front of the input,
+    /// so that a word that failed to parse can be read again. They are
+    /// taken out of the capture and the trace, and recorded again when they
+    /// are consumed again.
+    pub(crate) fn unread(&mut self, bytes: &[u8]) {
+        if let Some(capture) = &self.capture {
+            if let Ok(mut log) = capture.lock() {
+                log.unrecord(bytes.len());
+            }
+        }
+        if self.trace.enabled() {
+            self.trace.retreat(bytes);
+        }
+        let mut unread = bytes.to_vec();
+        unread.extend_from_slice(&self.unread[self.unread_pos..]);
+        self.unread = unread;
+        self.unread_pos = 0;
+        self.consumed -= bytes.len() as u64;
+    }
+
+    #[inline]
+    fn pending_unread(&self) -> &[u8] {
+        &self.unread[self.unread_pos..]
+    }
+}
+
+fn record(capture: &Option<Arc<Mutex<CaseLog>>>, trace: &Trace, bytes: &[u8]) {
+    if let Some(capture) = capture {
+        if let Ok(mut log) = capture.lock() {
+            log.record(bytes);
+        }
+    }
+    if trace.enabled() {
+        trace.advance(bytes);
+    }
+}
+
+impl<R: Read> Read for Counted<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if !self.pending_unread().is_empty() {
+            let n = self.pending_unread().read(buf)?;
+            self.unread_pos += n;
+            self.consumed += n as u64;
+            record(&self.capture, &self.trace, &buf[..n]);
+            return Ok(n);
+        }
+        let n = self.inner.read(buf)?;
+        self.consumed += n as u64;
+        record(&self.capture, &self.trace, &buf[..n]);
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for Counted<R> {
+    #[inline]
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.unread_pos < self.unread.len() {
+            return Ok(&self.unread[self.unread_pos..]);
+        }
+        if self.skip_bom {
+            self.skip_bom = false;
+            if self.consumed == 0 && self.inner.fill_buf()?.starts_with(BOM) {
+                self.consume(BOM.len());
+            }
+        }
+        let buf = self.inner.fill_buf()?;
+        if self.trace.enabled() {
+            self.trace.peek(buf);
+        }
+        Ok(buf)
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        self.consumed += amt as u64;
+        if self.unread_pos < self.unread.len() {
+            let end = (self.unread_pos + amt).min(self.unread.len());
+            record(&self.capture, &self.trace, &self.unread[self.unread_pos..end]);
+            self.unread_pos += amt;
+            if self.unread_pos >= self.unread.len() {
+                self.unread.clear();
+                self.unread_pos = 0;
+            }
+            return;
+        }
+        if self.capture.is_some() || self.trace.enabled() {
+            // the consumed bytes are still at the front of the buffer
+            if let Ok(buf) = self.inner.fill_buf() {
+                record(&self.capture, &self.trace, &buf[..amt.min(buf.len())]);
+            }
+        }
+        self.inner.consume(amt);
+    }
+}
@@ -0,0 +1,123 @@
+//! Reading of comma separated records with quoted fields (RFC 4180).
+
+use std::io::{self, BufRead};
+
+use crate::FormattedRead;
+
+impl<R: BufRead> FormattedRead<R> {
+    /// Read one comma separated record, terminated by a newline or the end of
+    /// the input.
+    ///
+    /// Fields may be enclosed in double quotes, in which case they can contain
+    /// commas and newlines, and `""` stands for a literal quote. Empty lines
+    /// before the record are skipped.
+    pub fn read_csv_record(&mut self) -> io::Result<Vec<String>> {
+        let res = self.scan_csv_record().map(|(fields, delim)| {
+            self.end_token(delim);
+            fields
+        });
+        self.count(res)
+    }
+
+    /// The fields of the next record, together with the line break ending
+    /// it, or `None` at the end of the input.
+    fn scan_csv_record(&mut self) -> io::Result<(Vec<String>, Option<u8>)> {
+        while let Some(c) = self.peek_byte()? {
+            if c != b'\n' && c != b'\r' {
+                break;
+            }
+            self.r.consume(1);
+        }
+        if self.peek_byte()?.is_none() {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+
+        let mut fields = vec![];
+        let mut field = vec![];
+        loop {
+            let mut end_of_record = false;
+            let mut delim = None;
+            if self.peek_byte()? == Some(b'"') {
+                self.r.consume(1);
+                loop {
+                    match self.next_byte()? {
+                        Some(b'"') => {
+                            if self.peek_byte()? == Some(b'"') {
+                                self.r.consume(1);
+                                field.push(b'"');
+                            } else {
+                                break;
+                            }
+                        }
+                        Some(c) => field.push(c),
+                        None => return Err(io::ErrorKind::UnexpectedEof.into()),
+                    }
+                }
+                match self.next_byte()? {
+                    Some(b',') => {}
+                    d @ (Some(b'\n') | None) => {
+                        end_of_record = true;
+                        delim = d;
+                    }
+                    Some(b'\r') if self.peek_byte()? == Some(b'\n') => {
+                        self.r.consume(1);
+                        end_of_record = true;
+                        delim = Some(b'\n');
+                    }
+                    Some(_) => return Err(io::ErrorKind::InvalidData.into()),
+                }
+            } else {
+                loop {
+                    match self.next_byte()? {
+                        Some(b',') => break,
+                        d @ (Some(b'\n') | None) => {
+                            end_of_record = true;
+                            delim = d;
+                            break;
+                        }
+                        Some(c) => field.push(c),
+                    }
+                }
+                // CR-LF
+                if end_of_record && field.last() == Some(&b'\r') {
+                    field.pop();
+                }
+            }
+
+            let s = String::from_utf8(std::mem::take(&mut field))
+                .map_err(|_| io::ErrorKind::InvalidData)?;
+            fields.push(s);
+            if end_of_record {
+                return Ok((fields, delim));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_csv() {
+    let input = b"3\r\nname,score\r\n\"Doe, John\",17\n\"say \"\"hi\"\"\",\"multi\nline\"\n,\n";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+
+    input! {
+        reader =>
+            n: usize,
+            records: [csvline; n],
+            last: csvline,
+    }
+
+    assert_eq!(records, vec![
+        vec!["name", "score"],
+        vec!["Doe, John", "17"],
+        vec!["say \"hi\"", "multi\nline"],
+    ]);
+    assert_eq!(last, vec!["", ""]);
+    assert_eq!(reader.tokens_read(), 5);
+    assert!(reader.read_csv_record().is_err());
+    assert_eq!(reader.tokens_read(), 5);
+
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&b"a,b\n7"[..]));
+    reader.read_csv_record().unwrap();
+    reader.expect_eol().unwrap();
+    assert_eq!(reader.read_word::<u32>().unwrap(), 7);
+}
@@ -0,0 +1,36 @@
+//! Words that are one of two types.
+
+use crate::{FromAscii, NumberFormat};
+
+/// A word that parses as an `A`, or otherwise as a `B`. Read with
+/// `either(A | B)`, e.g. `either(i64 | char)` for a number or `?`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Either<A, B> {
+    Left(A),
+    Right(B),
+}
+
+impl<A: FromAscii, B: FromAscii> FromAscii for Either<A, B> {
+    #[inline]
+    fn from_ascii(src: &[u8]) -> Option<Self> {
+        A::from_ascii(src).map(Either::Left).or_else(|| B::from_ascii(src).map(Either::Right))
+    }
+
+    #[inline]
+    fn from_ascii_fmt(src: &[u8], fmt: &NumberFormat) -> Option<Self> {
+        A::from_ascii_fmt(src, fmt).map(Either::Left).or_else(|| B::from_ascii_fmt(src, fmt).map(Either::Right))
+    }
+}
+
+#[test]
+fn test_either() {
+    use crate::FormattedRead;
+
+    let input = b"3 ? -7 ?? x";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+    input! { reader => a: [either(i64 | char); 3], b: either(u8 | String) }
+    assert_eq!(a, vec![Either::Left(3), Either::Right('?'), Either::Left(-7)]);
+    assert_eq!(b, Either::Right("??".to_string()));
+    assert!(reader.read_word::<Either<u32, f64>>().is_err());
+    assert_eq!(reader.read_word::<char>().unwrap(), 'x');
+}
@@ -0,0 +1,101 @@
+//! Binary payloads embedded in text as hex or base64 words.
+
+use crate::FromAscii;
+
+/// Bytes encoded as an even number of hex digits (either case).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Hex(pub Vec<u8>);
+
+impl FromAscii for Hex {
+    fn from_ascii(src: &[u8]) -> Option<Hex> {
+        if !src.len().is_multiple_of(2) {
+            return None;
+        }
+        src.chunks(2)
+            .map(|p| {
+                let hi = (p[0] as char).to_digit(16)?;
+                let lo = (p[1] as char).to_digit(16)?;
+                Some((hi << 4 | lo) as u8)
+            })
+            .collect::<Option<Vec<u8>>>()
+            .map(Hex)
+    }
+}
+
+/// Bytes encoded in standard base64, with or without `=` padding.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Base64(pub Vec<u8>);
+
+fn base64_digit(c: u8) -> Option<u32> {
+    Some(match c {
+        b'A'..=b'Z' => c - b'A',
+        b'a'..=b'z' => c - b'a' + 26,
+        b'0'..=b'9' => c - b'0' + 52,
+        b'+' => 62,
+        b'/' => 63,
+        _ => return None,
+    } as u32)
+}
+
+impl FromAscii for Base64 {
+    fn from_ascii(src: &[u8]) -> Option<Base64> {
+        let data = if src.len().is_multiple_of(4) {
+            let pad = src.iter().rev().take_while(|&&c| c == b'=').count();
+            if pad > 2 {
+                return None;
+            }
+            &src[..src.len() - pad]
+        } else {
+            src
+        };
+        if data.len() % 4 == 1 {
+            return None;
+        }
+
+        let mut res = Vec::with_capacity(data.len() * 3 / 4);
+        for chunk in data.chunks(4) {
+            let mut acc = 0;
+            for &c in chunk {
+                acc = acc << 6 | base64_digit(c)?;
+            }
+            // incomplete chunks encode 1 or 2 bytes with zero padding bits
+            let (bytes, bits) = match chunk.len() {
+                4 => (3, 0),
+                3 => (2, 2),
+                _ => (1, 4),
+            };
+            if acc & ((1 << bits) - 1) != 0 {
+                return None;
+            }
+            acc >>= bits;
+            for i in (0..bytes).rev() {
+                res.push((acc >> (8 * i)) as u8);
+            }
+        }
+        Some(Base64(res))
+    }
+}
+
+#[test]
+fn test_encoding() {
+    let input = b"00fFa1 aGVsbG8= aGVsbG8 TWFu\n";
+    let mut reader = crate::FormattedRead::new(std::io::Cursor::new(&input[..]));
+
+    input! {
+        reader =>
+            a: hex,
+            b, c, d: base64,
+    }
+
+    assert_eq!(a, vec![0x00, 0xff, 0xa1]);
+    assert_eq!(b, b"hello");
+    assert_eq!(c, b"hello");
+    assert_eq!(d, b"Man");
+
+    for bad in &["abc", "0g"] {
+        assert_eq!(Hex::from_ascii(bad.as_bytes()), None);
+    }
+    for bad in &["a", "aGVsbG9=", "aG=sbG8=", "a===", "aGVs*G8="] {
+        assert_eq!(Base64::from_ascii(bad.as_bytes()), None);
+    }
+}
@@ -0,0 +1,149 @@
+//! Integer arithmetic expressions such as `2 * (3 - -4)`.
+
+use std::io::{self, BufRead};
+
+use crate::FormattedRead;
+
+/// A binary operator of an `Expr`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl Op {
+    fn from_byte(c: u8) -> Option<Op> {
+        match c {
+            b'+' => Some(Op::Add),
+            b'-' => Some(Op::Sub),
+            b'*' => Some(Op::Mul),
+            b'/' => Some(Op::Div),
+            _ => None,
+        }
+    }
+
+    /// Left and right binding power, `*` and `/` bind tighter and all
+    /// operators are left associative.
+    fn binding_power(self) -> (u8, u8) {
+        match self {
+            Op::Add | Op::Sub => (1, 2),
+            Op::Mul | Op::Div => (3, 4),
+        }
+    }
+
+    pub fn apply(self, a: i64, b: i64) -> Option<i64> {
+        match self {
+            Op::Add => a.checked_add(b),
+            Op::Sub => a.checked_sub(b),
+            Op::Mul => a.checked_mul(b),
+            Op::Div => a.checked_div(b),
+        }
+    }
+}
+
+/// The syntax tree of an expression read by `FormattedRead::read_expr`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Expr {
+    Num(i64),
+    Neg(Box<Expr>),
+    Bin(Op, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    /// The value of the expression, with division rounding towards zero.
+    /// `None` on overflow or division by zero.
+    pub fn eval(&self) -> Option<i64> {
+        match self {
+            Expr::Num(x) => Some(*x),
+            Expr::Neg(e) => e.eval()?.checked_neg(),
+            Expr::Bin(op, a, b) => op.apply(a.eval()?, b.eval()?),
+        }
+    }
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+impl<R: BufRead> FormattedRead<R> {
+    /// Read an expression of integers, `+ - * /` and parentheses. It ends
+    /// at the end of the line, or before the first byte that cannot
+    /// continue it; inside parentheses it may span lines.
+    pub fn read_expr(&mut self) -> io::Result<Expr> {
+        self.skip_delims()?;
+        let res = self.expr_bp(0, 0)?;
+        self.end_token(None);
+        self.count(Ok(res))
+    }
+
+    /// Skip spaces, and line breaks inside parentheses, returning the next
+    /// byte.
+    fn expr_peek(&mut self, depth: usize) -> io::Result<Option<u8>> {
+        loop {
+            match self.peek_byte()? {
+                Some(b' ') | Some(b'\t') => self.r.consume(1),
+                Some(b'\n') | Some(b'\r') if depth > 0 => self.r.consume(1),
+                c => return Ok(c),
+            }
+        }
+    }
+
+    fn expr_bp(&mut self, min_bp: u8, depth: usize) -> io::Result<Expr> {
+        let mut lhs = match self.expr_peek(depth)? {
+            Some(b'(') => {
+                self.r.consume(1);
+                let e = self.expr_bp(0, depth + 1)?;
+                if self.expr_peek(depth + 1)? != Some(b')') {
+                    return Err(invalid("expected `)`"));
+                }
+                self.r.consume(1);
+                e
+            }
+            Some(b'-') => {
+                self.r.consume(1);
+                // unary minus binds tighter than any binary operator
+                Expr::Neg(Box::new(self.expr_bp(5, depth)?))
+            }
+            Some(c) if c.is_ascii_digit() => {
+                let mut x: i64 = 0;
+                while let Some(c) = self.peek_byte()?.filter(u8::is_ascii_digit) {
+                    x = x.checked_mul(10)
+                        .and_then(|x| x.checked_add((c - b'0') as i64))
+                        .ok_or_else(|| invalid("number too large"))?;
+                    self.r.consume(1);
+                }
+                Expr::Num(x)
+            }
+            Some(_) => return Err(invalid("expected a number or `(`")),
+            None => return Err(io::ErrorKind::UnexpectedEof.into()),
+        };
+        while let Some(op) = self.expr_peek(depth)?.and_then(Op::from_byte) {
+            let (l_bp, r_bp) = op.binding_power();
+            if l_bp < min_bp {
+                break;
+            }
+            self.r.consume(1);
+            let rhs = self.expr_bp(r_bp, depth)?;
+            lhs = Expr::Bin(op, Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+}
+
+#[test]
+fn test_expr() {
+    let input = b"1 + 2 * 3 - 4\n-(2 - 10) / 3*2\n(1 +\n 2) * -3 x\n7 / (3 - 3)";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+    input! { reader => a, b, c: expr, _: char, d: expr_tree }
+    assert_eq!((a, b, c), (3, 4, -9));
+    assert_eq!(d.eval(), None);
+
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&b"2 - 3 - 4"[..]));
+    let e = reader.read_expr().unwrap();
+    let num = |x| Box::new(Expr::Num(x));
+    assert_eq!(e, Expr::Bin(Op::Sub, Box::new(Expr::Bin(Op::Sub, num(2), num(3))), num(4)));
+    assert!(FormattedRead::new(&b"(1 + 2"[..]).read_expr().is_err());
+    assert!(FormattedRead::new(&b"1 + *"[..]).read_expr().is_err());
+}
@@ -0,0 +1,14 @@
+//! Integrations with other crates, each behind the feature of the same name.
+
+#[cfg(feature = "nalgebra")]
+mod nalgebra;
+#[cfg(feature = "ndarray")]
+mod ndarray;
+#[cfg(feature = "num-complex")]
+mod num_complex;
+#[cfg(feature = "ordered-float")]
+mod ordered_float;
+#[cfg(feature = "petgraph")]
+mod petgraph;
+#[cfg(feature = "tokio")]
+pub(crate) mod tokio;
@@ -0,0 +1,40 @@
+//! Reading statically sized `nalgebra` vectors and matrices.
+
+use std::io::{self, BufRead};
+
+use nalgebra::{SMatrix, SVector, Scalar};
+
+use crate::{FormattedRead, FromAscii};
+
+impl<R: BufRead> FormattedRead<R> {
+    /// Read the `D` components of a vector.
+    pub fn read_svector<T: FromAscii + Scalar, const D: usize>(&mut self) -> io::Result<SVector<T, D>> {
+        let data = (0..D).map(|_| self.read_word()).collect::<io::Result<Vec<T>>>()?;
+        Ok(SVector::from_row_slice(&data))
+    }
+
+    /// Read an `N` by `M` matrix given row by row.
+    pub fn read_smatrix<T: FromAscii + Scalar, const N: usize, const M: usize>(&mut self) -> io::Result<SMatrix<T, N, M>> {
+        let data = (0..N * M).map(|_| self.read_word()).collect::<io::Result<Vec<T>>>()?;
+        Ok(SMatrix::from_row_slice(&data))
+    }
+}
+
+#[test]
+fn test_nalgebra() {
+    use nalgebra::{Matrix2, Vector2, Vector3};
+
+    let input = b"1.5 -2 0.25\n3 4\n1 2\n3 4\n";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+
+    input! {
+        reader =>
+            a: vec3(f64),
+            b: svector(i64; 2),
+            m: smatrix(i32; 2, 2),
+    }
+
+    assert_eq!(a, Vector3::new(1.5, -2.0, 0.25));
+    assert_eq!(b, Vector2::new(3, 4));
+    assert_eq!(m, Matrix2::new(1, 2, 3, 4));
+}
@@ -0,0 +1,31 @@
+//! Reading matrices into `ndarray` arrays.
+
+use std::io::{self, BufRead};
+
+use ndarray::Array2;
+
+use crate::{FormattedRead, FromAscii};
+
+impl<R: BufRead> FormattedRead<R> {
+    /// Read an `n` by `m` matrix given row by row.
+    pub fn read_array2<T: FromAscii>(&mut self, n: usize, m: usize) -> io::Result<Array2<T>> {
+        let data = (0..n * m).map(|_| self.read_word()).collect::<io::Result<Vec<T>>>()?;
+        Ok(Array2::from_shape_vec((n, m), data).expect("shape matches length"))
+    }
+}
+
+#[test]
+fn test_array2() {
+    let input = b"2 3\n1 2 3\n4 5 6\n";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+
+    input! {
+        reader =>
+            n, m: usize,
+            a: array2(i32; n, m),
+    }
+
+    assert_eq!(a.dim(), (2, 3));
+    assert_eq!(a[[1, 0]], 4);
+    assert_eq!(a.row(0).to_vec(), vec![1, 2, 3]);
+}
@@ -0,0 +1,67 @@
+//! Reading `num_complex` complex numbers.
+
+use std::io::{self, BufRead};
+
+use num_complex::Complex;
+
+use crate::{FormattedRead, FromAscii, FromTokens};
+
+/// Read as the two words `re im`.
+impl<T: FromAscii> FromTokens for Complex<T> {
+    fn from_tokens<R: BufRead>(r: &mut FormattedRead<R>) -> io::Result<Self> {
+        Ok(Complex::new(r.read_word()?, r.read_word()?))
+    }
+}
+
+/// Parse a single word such as `3-4i`, `-2.5i`, `7` or `i`.
+fn parse_complex<T: FromAscii>(src: &[u8]) -> Option<Complex<T>> {
+    let body = match src.split_last() {
+        Some((b'i', body)) => body,
+        _ => return Some(Complex::new(T::from_ascii(src)?, T::from_ascii(b"0")?)),
+    };
+    // the sign in front of the imaginary part, skipping exponent signs
+    let split = (1..body.len())
+        .rev()
+        .find(|&i| (body[i] == b'+' || body[i] == b'-') && !matches!(body[i - 1], b'e' | b'E'));
+    let (re, im) = match split {
+        Some(i) => (T::from_ascii(&body[..i])?, &body[i..]),
+        None => (T::from_ascii(b"0")?, body),
+    };
+    let im = match im {
+        b"" | b"+" => T::from_ascii(b"1")?,
+        b"-" => T::from_ascii(b"-1")?,
+        _ => T::from_ascii(im)?,
+    };
+    Some(Complex::new(re, im))
+}
+
+impl<R: BufRead> FormattedRead<R> {
+    /// Read a complex number written as a single word `a+bi`.
+    pub fn read_complex<T: FromAscii>(&mut self) -> io::Result<Complex<T>> {
+        self.read_word_with(parse_complex)
+    }
+}
+
+#[test]
+fn test_complex() {
+    let input = b"1.5 -2\n3-4i -2.5i 7 i -i 1e-3+2e+1i\n";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+
+    input! {
+        reader =>
+            a: Complex<f64>,
+            b: [complex(f64); 6],
+    }
+
+    assert_eq!(a, Complex::new(1.5, -2.0));
+    assert_eq!(b, vec![
+        Complex::new(3.0, -4.0),
+        Complex::new(0.0, -2.5),
+        Complex::new(7.0, 0.0),
+        Complex::new(0.0, 1.0),
+        Complex::new(0.0, -1.0),
+        Complex::new(1e-3, 20.0),
+    ]);
+    assert!(parse_complex::<i64>(b"3+4").is_none());
+    assert!(parse_complex::<i64>(b"3+xi").is_none());
+}
@@ -0,0 +1,56 @@
+//! Reading `ordered_float` wrappers, so that floats can be sorted directly.
+
+use ordered_float::{NotNan, OrderedFloat};
+
+use crate::{FromAscii, NumberFormat};
+
+macro_rules! from_ascii_ordered_impl {
+    ($($t:ty)*) => {
+        $(
+            impl FromAscii for OrderedFloat<$t> {
+                #[inline]
+                fn from_ascii(src: &[u8]) -> Option<Self> {
+                    <$t>::from_ascii(src).map(OrderedFloat)
+                }
+
+                #[inline]
+                fn from_ascii_fmt(src: &[u8], fmt: &NumberFormat) -> Option<Self> {
+                    <$t>::from_ascii_fmt(src, fmt).map(OrderedFloat)
+                }
+            }
+
+            /// Rejects `NaN`.
+            impl FromAscii for NotNan<$t> {
+                #[inline]
+                fn from_ascii(src: &[u8]) -> Option<Self> {
+                    NotNan::new(<$t>::from_ascii(src)?).ok()
+                }
+
+                #[inline]
+                fn from_ascii_fmt(src: &[u8], fmt: &NumberFormat) -> Option<Self> {
+                    NotNan::new(<$t>::from_ascii_fmt(src, fmt)?).ok()
+                }
+            }
+        )*
+    }
+}
+from_ascii_ordered_impl! { f32 f64 }
+
+#[test]
+fn test_ordered_float() {
+    let input = b"3\n2.5 -1 0.75\n1.5 NaN\n";
+    let mut reader = crate::FormattedRead::new(std::io::Cursor::new(&input[..]));
+
+    input! {
+        reader =>
+            n: usize,
+            a: [OrderedFloat<f64>; n],
+            b: NotNan<f32>,
+    }
+
+    let mut a = a;
+    a.sort();
+    assert_eq!(a, vec![OrderedFloat(-1.0), OrderedFloat(0.75), OrderedFloat(2.5)]);
+    assert_eq!(b.into_inner(), 1.5);
+    assert!(reader.read_word::<NotNan<f64>>().is_err());
+}
@@ -0,0 +1,87 @@
+//! Reading edge lists into `petgraph` graphs.
+
+use std::io::{self, BufRead};
+
+use petgraph::csr::Csr;
+use petgraph::graph::{DiGraph, UnGraph};
+
+use crate::{FormattedRead, FromAscii};
+
+impl<R: BufRead> FormattedRead<R> {
+    fn read_edge<W: FromAscii>(&mut self, n: usize, weighted: bool) -> io::Result<(u32, u32, Option<W>)> {
+        let (u, v) = self.read_edge_endpoints(n)?;
+        let w = if weighted { Some(self.read_word()?) } else { None };
+        Ok((u as u32, v as u32, w))
+    }
+
+    /// Read `m` directed edges `u v` between the 1-indexed nodes `1..=n`.
+    pub fn read_petgraph(&mut self, n: usize, m: usize) -> io::Result<DiGraph<(), ()>> {
+        crate::checked_len::<u32, _>(self.max_alloc(), n.saturating_add(m))?;
+        let mut g = DiGraph::with_capacity(n, m);
+        for _ in 0..n {
+            g.add_node(());
+        }
+        for _ in 0..m {
+            let (u, v, _) = self.read_edge::<u8>(n, false)?;
+            g.add_edge(u.into(), v.into(), ());
+        }
+        Ok(g)
+    }
+
+    /// Like `read_petgraph`, but every edge is followed by its weight.
+    pub fn read_petgraph_weighted<W: FromAscii>(&mut self, n: usize, m: usize) -> io::Result<DiGraph<(), W>> {
+        crate::checked_len::<u32, _>(self.max_alloc(), n.saturating_add(m))?;
+        let mut g = DiGraph::with_capacity(n, m);
+        for _ in 0..n {
+            g.add_node(());
+        }
+        for _ in 0..m {
+            let (u, v, w) = self.read_edge(n, true)?;
+            g.add_edge(u.into(), v.into(), w.unwrap());
+        }
+        Ok(g)
+    }
+
+    /// Read `m` undirected edges `u v` between the 1-indexed nodes `1..=n`.
+    pub fn read_petgraph_undirected(&mut self, n: usize, m: usize) -> io::Result<UnGraph<(), ()>> {
+        Ok(self.read_petgraph(n, m)?.into_edge_type())
+    }
+
+    /// Read `m` directed edges `u v` into a compressed sparse row graph.
+    pub fn read_petgraph_csr(&mut self, n: usize, m: usize) -> io::Result<Csr<(), ()>> {
+        let mut edges = (0..m)
+            .map(|_| self.read_edge::<u8>(n, false).map(|(u, v, _)| (u, v)))
+            .collect::<io::Result<Vec<_>>>()?;
+        edges.sort_unstable();
+        let mut g: Csr<(), ()> = Csr::from_sorted_edges(&edges).expect("edges are sorted");
+        while g.node_count() < n {
+            g.add_node(());
+        }
+        Ok(g)
+    }
+}
+
+#[test]
+fn test_petgraph() {
+    let input = b"4 3\n1 2\n2 3\n3 1\n1 2 5\n4 3\n2 1\n";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+
+    input! {
+        reader =>
+            n, m: usize,
+            g: petgraph(n, m),
+            h: petgraph(n, 1; u64),
+            c: petgraph_csr(n, 2),
+    }
+
+    assert_eq!(g.node_count(), 4);
+    assert_eq!(g.edge_count(), 3);
+    assert!(g.contains_edge(2.into(), 0.into()));
+    assert_eq!(h.edge_weights().copied().collect::<Vec<_>>(), vec![5]);
+    assert_eq!(c.node_count(), 4);
+    assert_eq!(c.neighbors_slice(3), &[2]);
+    assert_eq!(c.neighbors_slice(1), &[0]);
+
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&b"1 3"[..]));
+    assert!(reader.read_petgraph_undirected(2, 1).is_err());
+}
@@ -0,0 +1,128 @@
+//! Reading from an async `tokio` source.
+
+use std::collections::VecDeque;
+use std::io::{self, BufRead, Read};
+use std::str::FromStr;
+
+use tokio::io::{AsyncBufRead, AsyncBufReadExt};
+
+use crate::{Delimiters, FormattedRead, FromAscii};
+
+/// The bytes received from the async source but not parsed yet.
+#[derive(Debug, Default)]
+pub struct Received {
+    buf: VecDeque<u8>,
+}
+
+impl Received {
+    fn push(&mut self, bytes: &[u8]) {
+        self.buf.extend(bytes);
+    }
+
+    /// Whether a whole word has arrived, followed by one of `delims`.
+    fn has_word(&self, delims: &Delimiters) -> bool {
+        let mut bytes = self.buf.iter().skip_while(|&&c| delims.contains(c));
+        bytes.next().is_some() && bytes.any(|&c| delims.contains(c))
+    }
+
+    /// Whether a whole line has arrived.
+    fn has_line(&self) -> bool {
+        self.buf.contains(&b'\n')
+    }
+}
+
+impl Read for Received {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        self.buf.read(out)
+    }
+}
+
+impl BufRead for Received {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        Ok(self.buf.as_slices().0)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.buf.drain(..amt);
+    }
+}
+
+/// Reader over an async source. Input is read until the next word or line
+/// has arrived completely and then parsed by a `FormattedRead` over the
+/// bytes received so far, so both readers split and parse it the same way.
+pub struct AsyncFormattedRead<R> {
+    r: R,
+    inner: FormattedRead<Received>,
+    eof: bool,
+}
+
+impl<R: AsyncBufRead + Unpin> AsyncFormattedRead<R> {
+    pub fn new(r: R) -> Self {
+        AsyncFormattedRead { r, inner: FormattedRead::new(Received::default()), eof: false }
+    }
+
+    /// Like `FormattedRead::with_delimiters`. Words are complete once one
+    /// of `delims` follows them.
+    pub fn with_delimiters(mut self, delims: &[u8]) -> Self {
+        self.inner.delims = Delimiters::new(delims);
+        self
+    }
+
+    async fn fill_until(&mut self, complete: fn(&FormattedRead<Received>) -> bool) -> io::Result<()> {
+        while !self.eof && !complete(&self.inner) {
+            let buf = self.r.fill_buf().await?;
+            let l = buf.len();
+            if l == 0 {
+                self.eof = true;
+            } else {
+                self.inner.r.inner.push(buf);
+                self.r.consume(l);
+            }
+        }
+        Ok(())
+    }
+
+    /// Like `FormattedRead::read_word`.
+    pub async fn read_word<T: FromAscii>(&mut self) -> io::Result<T> {
+        self.fill_until(|r| r.r.inner.has_word(&r.delims)).await?;
+        self.inner.read_word()
+    }
+
+    /// Like `FormattedRead::read_line`.
+    pub async fn read_line<T: FromStr>(&mut self) -> io::Result<T> {
+        self.fill_until(|r| r.r.inner.has_line()).await?;
+        self.inner.read_line()
+    }
+
+    /// The reader over the bytes received but not read yet, e.g. for an
+    /// `input!` spec once the rest of the line has arrived, which
+    /// `read_line::<String>` cannot do without consuming it.
+    pub fn buffered(&mut self) -> &mut FormattedRead<Received> {
+        &mut self.inner
+    }
+}
+
+#[test]
+fn test_async_reader() {
+    let rt = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    rt.block_on(async {
+        // chunks of 3 bytes split the words and lines
+        let src = tokio::io::BufReader::with_capacity(3, &b"12345 -7\nhello world\n  8 x 99"[..]);
+        let mut reader = AsyncFormattedRead::new(src);
+        assert_eq!(reader.read_word::<u32>().await.unwrap(), 12345);
+        assert_eq!(reader.read_word::<i8>().await.unwrap(), -7);
+        assert_eq!(reader.read_line::<String>().await.unwrap(), "hello world");
+        assert_eq!(reader.read_word::<u8>().await.unwrap(), 8);
+        assert!(reader.read_word::<u8>().await.is_err());
+        assert_eq!(reader.read_word::<char>().await.unwrap(), 'x');
+        assert_eq!(reader.read_word::<u64>().await.unwrap(), 99);
+        assert!(reader.read_word::<u64>().await.is_err());
+
+        // a pending source only completes a word with one of the delimiters
+        let (mut tx, rx) = tokio::io::duplex(16);
+        let mut reader = AsyncFormattedRead::new(tokio::io::BufReader::new(rx)).with_delimiters(b",");
+        tokio::io::AsyncWriteExt::write_all(&mut tx, b"1,22,").await.unwrap();
+        assert_eq!(reader.read_word::<u32>().await.unwrap(), 1);
+        assert_eq!(reader.read_word::<u32>().await.unwrap(), 22);
+    });
+}
@@ -0,0 +1,108 @@
+//! Downloading the sample tests of a problem from Codeforces or AtCoder.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+/// Turn the contents of a `<pre>` element back into text: line breaks and
+/// the per-line `<div>`s Codeforces uses become newlines, other tags are
+/// dropped and entities decoded.
+fn pre_text(html: &str) -> String {
+    let html = html.replace("<br />", "\n").replace("<br/>", "\n").replace("<br>", "\n").replace("</div>", "\n");
+    let mut text = String::new();
+    let mut rest = &html[..];
+    while let Some(open) = rest.find('<') {
+        text.push_str(&rest[..open]);
+        rest = rest[open..].find('>').map_or("", |close| &rest[open + close + 1..]);
+    }
+    text.push_str(rest);
+    let text = text.replace("&lt;", "<").replace("&gt;", ">").replace("&quot;", "\"").replace("&#39;", "'").replace("&amp;", "&");
+    let mut lines: Vec<&str> = text.lines().map(str::trim_end).collect();
+    while lines.first().is_some_and(|l| l.trim().is_empty()) {
+        lines.remove(0);
+    }
+    while lines.last().is_some_and(|l| l.trim().is_empty()) {
+        lines.pop();
+    }
+    lines.iter().map(|l| format!("{}\n", l)).collect()
+}
+
+/// The text of the first `<pre>` after each occurrence of `marker`.
+fn pre_after(html: &str, marker: &str) -> Vec<String> {
+    let mut out = vec![];
+    for (i, _) in html.match_indices(marker) {
+        let rest = &html[i..];
+        let found = rest.find("<pre").and_then(|start| {
+            let body = start + rest[start..].find('>')? + 1;
+            let end = body + rest[body..].find("</pre>")?;
+            Some(&rest[body..end])
+        });
+        if let Some(pre) = found {
+            out.push(pre_text(pre));
+        }
+    }
+    out
+}
+
+/// The sample inputs and outputs on the problem page `html`, from either
+/// Codeforces or AtCoder. On AtCoder the English statement is preferred.
+pub fn parse_samples(html: &str) -> Vec<(String, String)> {
+    let markers = [
+        ("class=\"input\"", "class=\"output\""),
+        (">Sample Input", ">Sample Output"),
+        (">入力例", ">出力例"),
+    ];
+    for (input, output) in markers.iter() {
+        let inputs = pre_after(html, input);
+        if !inputs.is_empty() {
+            return inputs.into_iter().zip(pre_after(html, output)).collect();
+        }
+    }
+    vec![]
+}
+
+/// Download the problem page at `url` with `curl` and write its samples
+/// to `dir` as `1.in`, `1.out`, `2.in`, ..., the layout `run_samples`
+/// reads. Returns the number of samples.
+pub fn fetch_samples(url: &str, dir: impl AsRef<Path>) -> io::Result<usize> {
+    let dir = dir.as_ref();
+    let output = Command::new("curl").args(["-sSfL", "-A", "Mozilla/5.0", url]).output()?;
+    if !output.status.success() {
+        let msg = format!("downloading {} failed: {}", url, String::from_utf8_lossy(&output.stderr).trim());
+        return Err(io::Error::other(msg));
+    }
+    let samples = parse_samples(&String::from_utf8_lossy(&output.stdout));
+    if samples.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, format!("no samples found at {}", url)));
+    }
+    fs::create_dir_all(dir)?;
+    for (i, (input, output)) in samples.iter().enumerate() {
+        fs::write(dir.join(format!("{}.in", i + 1)), input)?;
+        fs::write(dir.join(format!("{}.out", i + 1)), output)?;
+    }
+    Ok(samples.len())
+}
+
+#[test]
+fn test_parse_samples() {
+    let codeforces = r#"<div class="input-specification"><p>The first line contains <pre>n</pre></p></div>
+        <div class="sample-test"><div class="input"><div class="title">Input</div><pre>
+<div class="test-example-line test-example-line-even">3</div><div class="test-example-line">1 2 3</div>
+        </pre></div><div class="output"><div class="title">Output</div><pre>6
+</pre></div>
+        <div class="input"><div class="title">Input</div><pre>1<br />a &lt; b &amp;&amp; c</pre></div>
+        <div class="output"><div class="title">Output</div><pre>YES</pre></div></div>"#;
+    assert_eq!(
+        parse_samples(codeforces),
+        vec![("3\n1 2 3\n".to_string(), "6\n".to_string()), ("1\na < b && c\n".to_string(), "YES\n".to_string())],
+    );
+
+    let atcoder = r#"<span class="lang-ja"><h3>入力例 1</h3><pre>2 3
+</pre><h3>出力例 1</h3><pre>5
+</pre></span><span class="lang-en"><h3>Sample Input 1 <span class="btn-copy">Copy</span></h3><pre id="pre-sample0">2 3
+</pre><h3>Sample Output 1 <span class="btn-copy">Copy</span></h3><pre id="pre-sample1">5
+</pre></span>"#;
+    assert_eq!(parse_samples(atcoder), vec![("2 3\n".to_string(), "5\n".to_string())]);
+    assert!(parse_samples("<p>no samples</p>").is_empty());
+}
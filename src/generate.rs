@@ -0,0 +1,192 @@
+//! Random inputs for stress tests, in the formats the fragments read.
+
+use std::collections::HashSet;
+
+use crate::Rng;
+
+/// The edges of the tree on the nodes `1..=code.len() + 2` with the given
+/// 0-based Prüfer sequence.
+pub(crate) fn prufer_tree(code: &[usize]) -> Vec<(usize, usize)> {
+    let n = code.len() + 2;
+    let mut degree = vec![1; n];
+    for &v in code {
+        degree[v] += 1;
+    }
+    let mut edges = Vec::with_capacity(n - 1);
+    let mut ptr = degree.iter().position(|&d| d == 1).unwrap();
+    let mut leaf = ptr;
+    for &v in code {
+        edges.push((leaf + 1, v + 1));
+        degree[v] -= 1;
+        if degree[v] == 1 && v < ptr {
+            leaf = v;
+        } else {
+            ptr += 1;
+            while degree[ptr] != 1 {
+                ptr += 1;
+            }
+            leaf = ptr;
+        }
+    }
+    edges.push((leaf + 1, n));
+    edges
+}
+
+impl Rng {
+    /// A uniformly random permutation of `0..n`.
+    pub fn gen_perm(&mut self, n: usize) -> Vec<usize> {
+        let mut p: Vec<usize> = (0..n).collect();
+        self.shuffle(&mut p);
+        p
+    }
+
+    /// The `n - 1` edges of a uniformly random labeled tree on the nodes
+    /// `1..=n` (from a random Prüfer sequence), as read by
+    /// `graph(n, n - 1)`.
+    pub fn gen_tree(&mut self, n: usize) -> Vec<(usize, usize)> {
+        if n < 2 {
+            return vec![];
+        }
+        let code: Vec<usize> = (0..n - 2).map(|_| self.index(n)).collect();
+        let mut edges = prufer_tree(&code);
+        self.shuffle(&mut edges);
+        edges
+    }
+
+    /// `m` distinct edges `(u, v)` with `u != v`, one endpoint from each of
+    /// `us()` and `vs()`, also avoiding those in `taken`.
+    fn gen_edges<U, V>(&mut self, m: usize, taken: &mut HashSet<(usize, usize)>, mut us: U, mut vs: V) -> Vec<(usize, usize)>
+    where
+        U: FnMut(&mut Self) -> usize,
+        V: FnMut(&mut Self) -> usize,
+    {
+        let mut edges = Vec::with_capacity(m);
+        while edges.len() < m {
+            let (u, v) = (us(self), vs(self));
+            if u != v && taken.insert((u.min(v), u.max(v))) {
+                edges.push((u, v));
+            }
+        }
+        edges
+    }
+
+    /// A random connected simple graph on the nodes `1..=n` with `m`
+    /// edges, as read by `graph(n, m)`: a random tree plus random further
+    /// edges. Panics unless `n - 1 <= m <= n (n - 1) / 2`.
+    pub fn gen_connected_graph(&mut self, n: usize, m: usize) -> Vec<(usize, usize)> {
+        assert!(m + 1 >= n && m <= n * n.saturating_sub(1) / 2, "no connected simple graph with these sizes");
+        let mut edges = self.gen_tree(n);
+        let mut taken: HashSet<_> = edges.iter().map(|&(u, v)| (u.min(v), u.max(v))).collect();
+        let extra = m - edges.len();
+        edges.extend(self.gen_edges(extra, &mut taken, |r| r.index(n) + 1, |r| r.index(n) + 1));
+        self.shuffle(&mut edges);
+        edges
+    }
+
+    /// A random directed acyclic graph on the nodes `1..=n` with `m`
+    /// distinct edges, as read by `digraph(n, m)`. The edges go forward in
+    /// a random order of the nodes. Panics if `m > n (n - 1) / 2`.
+    pub fn gen_dag(&mut self, n: usize, m: usize) -> Vec<(usize, usize)> {
+        assert!(m <= n * n.saturating_sub(1) / 2, "too many edges for a DAG");
+        let order = self.gen_perm(n);
+        let edges = self.gen_edges(m, &mut HashSet::new(), |r| r.index(n), |r| r.index(n));
+        edges
+            .into_iter()
+            .map(|(a, b)| (order[a.min(b)] + 1, order[a.max(b)] + 1))
+            .collect()
+    }
+
+    /// A random bipartite graph with `m` distinct edges between the nodes
+    /// `1..=n1` and `n1 + 1..=n1 + n2`. Panics if `m > n1 n2`.
+    pub fn gen_bipartite(&mut self, n1: usize, n2: usize, m: usize) -> Vec<(usize, usize)> {
+        assert!(m <= n1 * n2, "too many edges for a bipartite graph");
+        self.gen_edges(m, &mut HashSet::new(), |r| r.index(n1) + 1, |r| n1 + r.index(n2) + 1)
+    }
+
+    /// A string of `n` bytes chosen uniformly from `charset`, e.g.
+    /// `b"ab"`.
+    pub fn gen_string(&mut self, n: usize, charset: &[u8]) -> String {
+        (0..n).map(|_| charset[self.index(charset.len())] as char).collect()
+    }
+}
+
+#[test]
+fn test_generate() {
+    let mut rng = Rng::new(7);
+    let mut p = rng.gen_perm(10);
+    p.sort_unstable();
+    assert_eq!(p, (0..10).collect::<Vec<_>>());
+
+    for n in 1..20 {
+        let edges = rng.gen_tree(n);
+        assert_eq!(edges.len(), n - 1);
+        // a tree on n nodes with n - 1 edges is connected
+        let mut root: Vec<usize> = (0..=n).collect();
+        fn find(root: &mut [usize], v: usize) -> usize {
+            if root[v] != v {
+                root[v] = find(root, root[v]);
+            }
+            root[v]
+        }
+        for &(u, v) in &edges {
+            let (ru, rv) = (find(&mut root, u), find(&mut root, v));
+            assert_ne!(ru, rv);
+            root[ru] = rv;
+        }
+    }
+
+    let s = rng.gen_string(50, b"xy");
+    assert!(s.len() == 50 && s.bytes().all(|c| c == b'x' || c == b'y'));
+}
+
+#[test]
+fn test_generate_graphs() {
+    use crate::{FormattedRead, InputWriter};
+
+    let mut rng = Rng::new(3);
+    // every labeled tree on 3 nodes is a path, its middle node has degree 2
+    let mut middle = [0; 3];
+    for _ in 0..3000 {
+        let mut deg = [0; 3];
+        for (u, v) in rng.gen_tree(3) {
+            deg[u - 1] += 1;
+            deg[v - 1] += 1;
+        }
+        middle[deg.iter().position(|&d| d == 2).unwrap()] += 1;
+    }
+    assert!(middle.iter().all(|&c| c > 900), "{:?}", middle);
+
+    let edges = rng.gen_connected_graph(6, 10);
+    let mut w = InputWriter::new();
+    w.put(&edges);
+    let mut reader = FormattedRead::new(w.as_bytes());
+    input! { reader => adj: graph(6, 10; dedup, no_loops) }
+    assert_eq!(adj.iter().map(Vec::len).sum::<usize>(), 20);
+
+    let dag = rng.gen_dag(5, 10);
+    assert_eq!(dag.len(), 10);
+    let mut succ = vec![vec![]; 5];
+    for &(u, v) in &dag {
+        succ[u - 1].push(v - 1);
+    }
+    // no node of a DAG lies on a cycle, checked by a topological sort
+    let mut indeg = [0; 5];
+    for &(_, v) in &dag {
+        indeg[v - 1] += 1;
+    }
+    let mut stack: Vec<usize> = (0..5).filter(|&v| indeg[v] == 0).collect();
+    let mut seen = 0;
+    while let Some(u) = stack.pop() {
+        seen += 1;
+        for &v in &succ[u] {
+            indeg[v] -= 1;
+            if indeg[v] == 0 {
+                stack.push(v);
+            }
+        }
+    }
+    assert_eq!(seen, 5);
+
+    let bip = rng.gen_bipartite(2, 3, 6);
+    assert!(bip.iter().all(|&(u, v)| u <= 2 && v > 2));
+}
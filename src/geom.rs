@@ -0,0 +1,240 @@
+//! Points for computational geometry and directions for grids.
+
+use std::io::{self, BufRead};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::{FormattedRead, FromAscii, FromTokens};
+
+/// A point (or vector) in the plane, read as its two coordinates `x y`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// A point (or vector) in space, read as its three coordinates `x y z`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point2<T> {
+    pub fn new(x: T, y: T) -> Self {
+        Point2 { x, y }
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>> Point2<T> {
+    pub fn dot(self, o: Self) -> T {
+        self.x * o.x + self.y * o.y
+    }
+
+    /// The z component of the cross product, positive if `o` is
+    /// counterclockwise from `self`.
+    pub fn cross(self, o: Self) -> T {
+        self.x * o.y - self.y * o.x
+    }
+
+    /// Squared euclidean length.
+    pub fn norm2(self) -> T {
+        self.dot(self)
+    }
+}
+
+impl<T> Point3<T> {
+    pub fn new(x: T, y: T, z: T) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>> Point3<T> {
+    pub fn dot(self, o: Self) -> T {
+        self.x * o.x + self.y * o.y + self.z * o.z
+    }
+
+    pub fn cross(self, o: Self) -> Self {
+        Point3 {
+            x: self.y * o.z - self.z * o.y,
+            y: self.z * o.x - self.x * o.z,
+            z: self.x * o.y - self.y * o.x,
+        }
+    }
+
+    /// Squared euclidean length.
+    pub fn norm2(self) -> T {
+        self.dot(self)
+    }
+}
+
+macro_rules! point_ops_impl {
+    ($p:ident { $($c:ident),* }) => {
+        impl<T: Add<Output = T>> Add for $p<T> {
+            type Output = Self;
+            fn add(self, o: Self) -> Self {
+                $p { $($c: self.$c + o.$c),* }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $p<T> {
+            type Output = Self;
+            fn sub(self, o: Self) -> Self {
+                $p { $($c: self.$c - o.$c),* }
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $p<T> {
+            type Output = Self;
+            fn neg(self) -> Self {
+                $p { $($c: -self.$c),* }
+            }
+        }
+
+        /// Scaling by a scalar.
+        impl<T: Copy + Mul<Output = T>> Mul<T> for $p<T> {
+            type Output = Self;
+            fn mul(self, k: T) -> Self {
+                $p { $($c: self.$c * k),* }
+            }
+        }
+
+        impl<T: AddAssign> AddAssign for $p<T> {
+            fn add_assign(&mut self, o: Self) {
+                $(self.$c += o.$c;)*
+            }
+        }
+
+        impl<T: SubAssign> SubAssign for $p<T> {
+            fn sub_assign(&mut self, o: Self) {
+                $(self.$c -= o.$c;)*
+            }
+        }
+
+        impl<T: FromAscii> FromTokens for $p<T> {
+            fn from_tokens<R: BufRead>(r: &mut FormattedRead<R>) -> io::Result<Self> {
+                Ok($p { $($c: r.read_word()?),* })
+            }
+        }
+    }
+}
+point_ops_impl! { Point2 { x, y } }
+point_ops_impl! { Point3 { x, y, z } }
+
+/// One of the four axis-parallel directions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// The letters used for the directions in the input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DirSet {
+    /// `U`, `D`, `L`, `R`
+    Udlr,
+    /// `N`, `S`, `W`, `E`
+    Nsew,
+    /// `^`, `v`, `<`, `>`
+    Arrows,
+}
+
+impl Dir {
+    pub const ALL: [Dir; 4] = [Dir::Up, Dir::Down, Dir::Left, Dir::Right];
+
+    /// Parse a direction from the letters of `set`.
+    pub fn from_byte(c: u8, set: DirSet) -> Option<Dir> {
+        let letters = match set {
+            DirSet::Udlr => b"UDLR",
+            DirSet::Nsew => b"NSWE",
+            DirSet::Arrows => b"^v<>",
+        };
+        letters.iter().position(|&x| x == c).map(|i| Dir::ALL[i])
+    }
+
+    /// `(dx, dy)` with the y axis pointing up, e.g. `(0, 1)` for `Up`.
+    pub fn delta(self) -> (i64, i64) {
+        match self {
+            Dir::Up => (0, 1),
+            Dir::Down => (0, -1),
+            Dir::Left => (-1, 0),
+            Dir::Right => (1, 0),
+        }
+    }
+
+    /// `(row, column)` offset in a grid whose first row is at the top,
+    /// e.g. `(-1, 0)` for `Up`.
+    pub fn grid_delta(self) -> (i64, i64) {
+        let (dx, dy) = self.delta();
+        (-dy, dx)
+    }
+
+    pub fn opposite(self) -> Dir {
+        match self {
+            Dir::Up => Dir::Down,
+            Dir::Down => Dir::Up,
+            Dir::Left => Dir::Right,
+            Dir::Right => Dir::Left,
+        }
+    }
+
+    /// Parse a whole word of moves such as `UURDL`.
+    pub fn parse_moves(src: &[u8], set: DirSet) -> Option<Vec<Dir>> {
+        src.iter().map(|&c| Dir::from_byte(c, set)).collect()
+    }
+}
+
+/// Accepts a single letter of any `DirSet`.
+impl FromAscii for Dir {
+    fn from_ascii(src: &[u8]) -> Option<Dir> {
+        match *src {
+            [c] => Dir::from_byte(c, DirSet::Udlr)
+                .or_else(|| Dir::from_byte(c, DirSet::Nsew))
+                .or_else(|| Dir::from_byte(c, DirSet::Arrows)),
+            _ => None,
+        }
+    }
+}
+
+#[test]
+fn test_points() {
+    let input = b"3\n0 0\n4 0\n0 3\n1 2 3\n";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+
+    input! {
+        reader =>
+            n: usize,
+            pts: [Point2<i64>; n],
+            q: Point3<f64>,
+    }
+
+    let (a, b, c) = (pts[0], pts[1], pts[2]);
+    assert_eq!(b - a, Point2::new(4, 0));
+    assert_eq!((b - a).cross(c - a), 12);
+    assert_eq!((c - b).norm2(), 25);
+    assert_eq!(-b * 2 + c, Point2::new(-8, 3));
+    assert_eq!(q.cross(Point3::new(1.0, 0.0, 0.0)), Point3::new(0.0, 3.0, -2.0));
+}
+
+#[test]
+fn test_dir() {
+    let input = b"3\nU D >\nNNEW\n";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+
+    input! {
+        reader =>
+            n: usize,
+            a: [Dir; n],
+            b: moves(Nsew),
+    }
+
+    assert_eq!(a, vec![Dir::Up, Dir::Down, Dir::Right]);
+    assert_eq!(b, vec![Dir::Up, Dir::Up, Dir::Right, Dir::Left]);
+    assert_eq!(a[0].delta(), (0, 1));
+    assert_eq!(a[0].grid_delta(), (-1, 0));
+    assert_eq!(a[2].opposite(), Dir::Left);
+    assert_eq!(Dir::parse_moves(b"UX", DirSet::Udlr), None);
+    assert_eq!(Dir::from_byte(b'N', DirSet::Udlr), None);
+}
@@ -0,0 +1,254 @@
+//! Reading edge lists into adjacency lists.
+
+use std::collections::HashSet;
+use std::io::{self, BufRead};
+
+use crate::FormattedRead;
+
+/// Adjacency lists of a graph on the nodes `0..n`.
+pub type Adj = Vec<Vec<usize>>;
+
+/// Adjacency lists of `(neighbor, edge id)` pairs, the edges being numbered
+/// from 0 in input order.
+pub type AdjIds = Vec<Vec<(usize, usize)>>;
+
+/// Which edges to leave out while building a graph, to get a simple graph.
+/// In `input!`, the flags follow the graph size, as in
+/// `graph(n, m; no_loops, dedup)`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EdgeFilter {
+    no_loops: bool,
+    dedup: bool,
+}
+
+impl EdgeFilter {
+    pub fn new() -> Self {
+        EdgeFilter::default()
+    }
+
+    /// Drop edges from a node to itself.
+    pub fn no_loops(mut self) -> Self {
+        self.no_loops = true;
+        self
+    }
+
+    /// Keep only the first of several parallel edges.
+    pub fn dedup(mut self) -> Self {
+        self.dedup = true;
+        self
+    }
+}
+
+/// Number of edges dropped by an `EdgeFilter`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Dropped {
+    pub self_loops: usize,
+    pub parallel: usize,
+}
+
+/// In-degrees of the nodes of a functional graph, in which node `i` has the
+/// only outgoing edge to `next[i]`.
+pub fn in_degrees(next: &[usize]) -> Vec<usize> {
+    let mut indeg = vec![0; next.len()];
+    for &v in next {
+        indeg[v] += 1;
+    }
+    indeg
+}
+
+/// Which nodes of a functional graph lie on a cycle.
+pub fn cycle_nodes(next: &[usize]) -> Vec<bool> {
+    let mut indeg = in_degrees(next);
+    let mut on_cycle = vec![true; next.len()];
+    let mut stack: Vec<usize> = (0..next.len()).filter(|&v| indeg[v] == 0).collect();
+    while let Some(v) = stack.pop() {
+        on_cycle[v] = false;
+        let w = next[v];
+        indeg[w] -= 1;
+        if indeg[w] == 0 {
+            stack.push(w);
+        }
+    }
+    on_cycle
+}
+
+impl<R: BufRead> FormattedRead<R> {
+    /// Read the 1-indexed successors `f[1], ..., f[n]` of the nodes of a
+    /// functional graph, returned 0-indexed.
+    pub fn read_func_graph(&mut self, n: usize) -> io::Result<Vec<usize>> {
+        (0..n)
+            .map(|_| match self.read_word::<usize>()? {
+                v if v >= 1 && v <= n => Ok(v - 1),
+                _ => Err(io::Error::new(io::ErrorKind::InvalidData, "successor out of range")),
+            })
+            .collect()
+    }
+
+    /// Like `read_graph`, but with the index of every edge.
+    pub fn read_graph_with_ids(&mut self, n: usize, m: usize) -> io::Result<AdjIds> {
+        let mut adj = self.adj_lists(n)?;
+        for id in 0..m {
+            let (u, v) = self.read_edge_endpoints(n)?;
+            adj[u].push((v, id));
+            adj[v].push((u, id));
+        }
+        Ok(adj)
+    }
+
+    /// Like `read_digraph`, but with the index of every edge.
+    pub fn read_digraph_with_ids(&mut self, n: usize, m: usize) -> io::Result<AdjIds> {
+        let mut adj = self.adj_lists(n)?;
+        for id in 0..m {
+            let (u, v) = self.read_edge_endpoints(n)?;
+            adj[u].push((v, id));
+        }
+        Ok(adj)
+    }
+
+    fn read_filtered(&mut self, n: usize, m: usize, directed: bool, filter: EdgeFilter) -> io::Result<(Adj, Dropped)> {
+        let mut adj = self.adj_lists(n)?;
+        let mut dropped = Dropped::default();
+        let mut seen = HashSet::new();
+        for _ in 0..m {
+            let (u, v) = self.read_edge_endpoints(n)?;
+            if filter.no_loops && u == v {
+                dropped.self_loops += 1;
+                continue;
+            }
+            let key = if directed { (u, v) } else { (u.min(v), u.max(v)) };
+            if filter.dedup && !seen.insert(key) {
+                dropped.parallel += 1;
+                continue;
+            }
+            adj[u].push(v);
+            if !directed {
+                adj[v].push(u);
+            }
+        }
+        Ok((adj, dropped))
+    }
+
+    /// Like `read_graph`, but leave out the edges rejected by `filter` and
+    /// count them.
+    pub fn read_graph_filtered(&mut self, n: usize, m: usize, filter: EdgeFilter) -> io::Result<(Adj, Dropped)> {
+        self.read_filtered(n, m, false, filter)
+    }
+
+    /// Like `read_digraph`, but leave out the edges rejected by `filter` and
+    /// count them. Edges `u v` and `v u` are not parallel.
+    pub fn read_digraph_filtered(&mut self, n: usize, m: usize, filter: EdgeFilter) -> io::Result<(Adj, Dropped)> {
+        self.read_filtered(n, m, true, filter)
+    }
+
+    /// `n` empty adjacency lists, if they fit into the allocation limit.
+    fn adj_lists<T: Clone>(&self, n: usize) -> io::Result<Vec<Vec<T>>> {
+        Ok(vec![vec![]; crate::checked_len::<Vec<T>, _>(self.max_alloc(), n)?])
+    }
+
+    /// Read the two 1-indexed endpoints `u v` of an edge between the nodes
+    /// `1..=n`, returned 0-indexed.
+    pub(crate) fn read_edge_endpoints(&mut self, n: usize) -> io::Result<(usize, usize)> {
+        let u: usize = self.read_word()?;
+        let v: usize = self.read_word()?;
+        if u == 0 || v == 0 || u > n || v > n {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "edge endpoint out of range"));
+        }
+        Ok((u - 1, v - 1))
+    }
+
+    /// Read `m` undirected edges `u v` between the 1-indexed nodes `1..=n`
+    /// into 0-indexed adjacency lists.
+    pub fn read_graph(&mut self, n: usize, m: usize) -> io::Result<Adj> {
+        let mut adj = self.adj_lists(n)?;
+        for _ in 0..m {
+            let (u, v) = self.read_edge_endpoints(n)?;
+            adj[u].push(v);
+            adj[v].push(u);
+        }
+        Ok(adj)
+    }
+
+    /// Like `read_graph`, but the edges are directed from `u` to `v`.
+    pub fn read_digraph(&mut self, n: usize, m: usize) -> io::Result<Adj> {
+        let mut adj = self.adj_lists(n)?;
+        for _ in 0..m {
+            let (u, v) = self.read_edge_endpoints(n)?;
+            adj[u].push(v);
+        }
+        Ok(adj)
+    }
+
+    /// Like `read_digraph`, but also build the adjacency lists of the
+    /// reversed graph, e.g. for strongly connected components.
+    pub fn read_digraph_rev(&mut self, n: usize, m: usize) -> io::Result<(Adj, Adj)> {
+        let mut adj = self.adj_lists(n)?;
+        let mut radj = self.adj_lists(n)?;
+        for _ in 0..m {
+            let (u, v) = self.read_edge_endpoints(n)?;
+            adj[u].push(v);
+            radj[v].push(u);
+        }
+        Ok((adj, radj))
+    }
+}
+
+#[test]
+fn test_adjacency() {
+    let input = b"3 2\n1 2\n2 3\n3 3\n1 2\n3 1\n2 3\n";
+    let mut reader = FormattedRead::new(io::Cursor::new(&input[..]));
+    input! {
+        reader =>
+            n, m: usize,
+            g: graph(n, m),
+            d: digraph(3, 1),
+            (adj, radj): digraph(3, 2; rev),
+    }
+    assert_eq!(g, vec![vec![1], vec![0, 2], vec![1]]);
+    assert_eq!(d, vec![vec![], vec![], vec![2]]);
+    assert_eq!(adj, vec![vec![1], vec![], vec![0]]);
+    assert_eq!(radj, vec![vec![2], vec![0], vec![]]);
+    assert!(reader.read_graph(2, 1).is_err());
+}
+
+#[test]
+fn test_edge_filter() {
+    let input = b"1 2\n2 1\n3 3\n1 2\n1 2\n2 1\n2 2\n1 2 3 3";
+    let mut reader = FormattedRead::new(io::Cursor::new(&input[..]));
+    let (g, dropped) = reader.read_graph_filtered(3, 4, EdgeFilter::new().no_loops().dedup()).unwrap();
+    assert_eq!(g, vec![vec![1], vec![0], vec![]]);
+    assert_eq!(dropped, Dropped { self_loops: 1, parallel: 2 });
+    input! {
+        reader =>
+            d: digraph(2, 3; dedup),
+            l: graph(3, 2; no_loops),
+    }
+    assert_eq!(d, vec![vec![1], vec![0, 1]]);
+    assert_eq!(l, vec![vec![1], vec![0], vec![]]);
+}
+
+#[test]
+fn test_func_graph() {
+    let mut reader = FormattedRead::new(io::Cursor::new(&b"2 3 1 3 4\n2 1 1\n1"[..]));
+    input! {
+        reader =>
+            f: func_graph(5),
+            (g, indeg): func_graph(3; indeg),
+            (h, cyc): func_graph(1; cycles),
+    }
+    assert_eq!(f, vec![1, 2, 0, 2, 3]);
+    assert_eq!(cycle_nodes(&f), vec![true, true, true, false, false]);
+    assert_eq!((g, indeg), (vec![1, 0, 0], vec![2, 1, 0]));
+    assert_eq!((h, cyc), (vec![0], vec![true]));
+}
+
+#[test]
+fn test_edge_ids() {
+    let mut reader = FormattedRead::new(io::Cursor::new(&b"1 2\n2 3\n1 2\n2 1\n"[..]));
+    input! {
+        reader =>
+            g: graph(3, 2; ids),
+            d: digraph(2, 2; ids),
+    }
+    assert_eq!(g, vec![vec![(1, 0)], vec![(0, 0), (2, 1)], vec![(1, 1)]]);
+    assert_eq!(d, vec![vec![(1, 0)], vec![(0, 1)]]);
+}
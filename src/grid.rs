@@ -0,0 +1,101 @@
+//! Character grids.
+
+use std::io::{self, BufRead};
+
+use crate::FormattedRead;
+
+/// The offsets `(dx, dy)` of the 4 orthogonal neighbors of a cell.
+pub const DIRS4: [(i64, i64); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+/// The offsets `(dx, dy)` of the 8 neighbors of a cell, including the
+/// diagonal ones.
+pub const DIRS8: [(i64, i64); 8] = [(-1, 0), (-1, 1), (0, 1), (1, 1), (1, 0), (1, -1), (0, -1), (-1, -1)];
+
+impl<R: BufRead> FormattedRead<R> {
+    fn read_grid_row(&mut self, m: usize, row: &mut Vec<u8>) -> io::Result<()> {
+        self.read_word_with(|w| {
+            if w.len() == m {
+                row.extend_from_slice(w);
+                Some(())
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Read `n` words of exactly `m` bytes each, the rows of a grid such as
+    /// `#..#`.
+    pub fn read_grid(&mut self, n: usize, m: usize) -> io::Result<Vec<Vec<u8>>> {
+        crate::checked_len::<u8, _>(self.max_alloc(), n.saturating_mul(m))?;
+        (0..n)
+            .map(|_| {
+                let mut row = Vec::with_capacity(m);
+                self.read_grid_row(m, &mut row)?;
+                Ok(row)
+            })
+            .collect()
+    }
+
+    /// Like `read_grid`, but surround the grid with a frame of `border`
+    /// bytes, giving `n + 2` rows of length `m + 2`, so that searches from
+    /// the inner cells need no bounds checks.
+    pub fn read_grid_bordered(&mut self, n: usize, m: usize, border: u8) -> io::Result<Vec<Vec<u8>>> {
+        crate::checked_len::<u8, _>(self.max_alloc(), n.saturating_add(2).saturating_mul(m.saturating_add(2)))?;
+        let mut grid = Vec::with_capacity(n + 2);
+        grid.push(vec![border; m + 2]);
+        for _ in 0..n {
+            let mut row = Vec::with_capacity(m + 2);
+            row.push(border);
+            self.read_grid_row(m, &mut row)?;
+            row.push(border);
+            grid.push(row);
+        }
+        grid.push(vec![border; m + 2]);
+        Ok(grid)
+    }
+
+    /// Read `k` offsets `dx dy`, such as the allowed moves of a piece. With
+    /// a `bound` of `(n, m)` they must satisfy `|dx| < n` and `|dy| < m`,
+    /// so that they can lead somewhere in an `n` by `m` grid.
+    pub fn read_offsets(&mut self, k: usize, bound: Option<(usize, usize)>) -> io::Result<Vec<(i64, i64)>> {
+        (0..k)
+            .map(|_| {
+                let d: (i64, i64) = (self.read_word()?, self.read_word()?);
+                match bound {
+                    Some((n, m)) if d.0.unsigned_abs() >= n as u64 || d.1.unsigned_abs() >= m as u64 => {
+                        let msg = format!("offset {} {} is out of bounds", d.0, d.1);
+                        Err(io::Error::new(io::ErrorKind::InvalidData, msg))
+                    }
+                    _ => Ok(d),
+                }
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn test_grid() {
+    let input = b"2 3\n#.#\n..#\n.#\n#.\n";
+    let mut reader = FormattedRead::new(io::Cursor::new(&input[..]));
+    input! {
+        reader =>
+            n, m: usize,
+            g: grid(n, m),
+            b: grid(2, 2; border = b'#'),
+    }
+    assert_eq!(g, vec![b"#.#".to_vec(), b"..#".to_vec()]);
+    assert_eq!(b, vec![b"####".to_vec(), b"#.##".to_vec(), b"##.#".to_vec(), b"####".to_vec()]);
+
+    let mut reader = FormattedRead::new(io::Cursor::new(&b"..\n..."[..]));
+    assert!(reader.read_grid(2, 2).is_err());
+}
+
+#[test]
+fn test_offsets() {
+    let input = b"2 1 2 -2 -1 1 5 0";
+    let mut reader = FormattedRead::new(io::Cursor::new(&input[..]));
+    input! { reader => k: usize, moves: offsets(k; bound = (3, 3)) }
+    assert_eq!(moves, vec![(1, 2), (-2, -1)]);
+    assert!(reader.read_offsets(1, Some((3, 3))).is_err());
+    assert_eq!(DIRS4.iter().chain(&DIRS8).map(|d| d.0 + d.1).sum::<i64>(), 0);
+}
@@ -0,0 +1,169 @@
+//! Writing values in the format `input!` reads them, to check that both
+//! directions agree.
+
+use std::fmt::Debug;
+
+use crate::rng::Rng;
+use crate::FormattedRead;
+
+/// Values that can be written as input tokens.
+pub trait ToInput {
+    fn write_input(&self, w: &mut InputWriter);
+}
+
+/// Buffer of generated input, separating tokens by spaces.
+#[derive(Default)]
+pub struct InputWriter {
+    out: Vec<u8>,
+    line_start: bool,
+}
+
+impl InputWriter {
+    pub fn new() -> Self {
+        InputWriter { out: vec![], line_start: true }
+    }
+
+    /// Append a single token.
+    pub fn token(&mut self, token: &[u8]) -> &mut Self {
+        if !self.line_start {
+            self.out.push(b' ');
+        }
+        self.out.extend_from_slice(token);
+        self.line_start = false;
+        self
+    }
+
+    /// Append the tokens of `value`.
+    pub fn put<T: ToInput + ?Sized>(&mut self, value: &T) -> &mut Self {
+        value.write_input(self);
+        self
+    }
+
+    pub fn newline(&mut self) -> &mut Self {
+        self.out.push(b'\n');
+        self.line_start = true;
+        self
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.out
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.out
+    }
+}
+
+macro_rules! to_input_display_impl {
+    ($($t:ty)*) => {
+        $(
+            impl ToInput for $t {
+                fn write_input(&self, w: &mut InputWriter) {
+                    w.token(self.to_string().as_bytes());
+                }
+            }
+        )*
+    };
+}
+
+to_input_display_impl!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize f32 f64 char String str);
+
+impl<T: ToInput> ToInput for [T] {
+    fn write_input(&self, w: &mut InputWriter) {
+        for x in self {
+            x.write_input(w);
+        }
+    }
+}
+
+impl<T: ToInput> ToInput for Vec<T> {
+    fn write_input(&self, w: &mut InputWriter) {
+        self[..].write_input(w);
+    }
+}
+
+impl<T: ToInput, const N: usize> ToInput for [T; N] {
+    fn write_input(&self, w: &mut InputWriter) {
+        self[..].write_input(w);
+    }
+}
+
+impl<T: ToInput + ?Sized> ToInput for &T {
+    fn write_input(&self, w: &mut InputWriter) {
+        (**self).write_input(w);
+    }
+}
+
+macro_rules! to_input_tuple_impl {
+    ($($t:ident $i:tt),*) => {
+        impl<$($t: ToInput),*> ToInput for ($($t,)*) {
+            fn write_input(&self, w: &mut InputWriter) {
+                $(self.$i.write_input(w);)*
+            }
+        }
+    };
+}
+
+to_input_tuple_impl!(A 0);
+to_input_tuple_impl!(A 0, B 1);
+to_input_tuple_impl!(A 0, B 1, C 2);
+to_input_tuple_impl!(A 0, B 1, C 2, D 3);
+to_input_tuple_impl!(A 0, B 1, C 2, D 3, E 4);
+to_input_tuple_impl!(A 0, B 1, C 2, D 3, E 4, F 5);
+
+/// For `cases` values made by `gen`, write each with `InputWriter` and
+/// check that `parse` reads it back unchanged. `parse` usually consists of
+/// the `input!` spec of the problem.
+pub fn assert_round_trip<T, G, P>(cases: usize, seed: u64, mut gen: G, mut parse: P)
+where
+    T: ToInput + PartialEq + Debug,
+    G: FnMut(&mut Rng) -> T,
+    P: FnMut(&mut FormattedRead<&[u8]>) -> T,
+{
+    let mut rng = Rng::new(seed);
+    for case in 0..cases {
+        let value = gen(&mut rng);
+        let mut w = InputWriter::new();
+        w.put(&value).newline();
+        let mut reader = FormattedRead::new(w.as_bytes());
+        let parsed = parse(&mut reader);
+        assert_eq!(
+            parsed, value,
+            "case {} does not round-trip, input: {:?}",
+            case, String::from_utf8_lossy(w.as_bytes()),
+        );
+    }
+}
+
+#[test]
+fn test_round_trip() {
+    assert_round_trip(
+        200,
+        1,
+        |rng| {
+            let n = rng.range(0, 10) as usize;
+            let a: Vec<i64> = (0..n).map(|_| rng.range(i64::MIN, i64::MAX)).collect();
+            (n, a)
+        },
+        |r| {
+            input! { r => n: usize, a: [i64; n] }
+            (n, a)
+        },
+    );
+    assert_round_trip(
+        200,
+        2,
+        |rng| {
+            let s: String = (0..rng.range(1, 5)).map(|_| (b'a' + rng.range(0, 25) as u8) as char).collect();
+            (s, rng.range(0, 255) as u8, f64::from_bits(rng.next_u64() >> 2))
+        },
+        |r| {
+            input! { r => s: String, c: u8, x: f64 }
+            (s, c, x)
+        },
+    );
+
+    let mut w = InputWriter::new();
+    w.put(&(2, [1u8, 2])).newline().put("ab");
+    assert_eq!(w.into_bytes(), b"2 1 2\nab");
+}
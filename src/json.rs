@@ -0,0 +1,121 @@
+//! JSON values embedded in the input.
+
+use std::io::{self, BufRead};
+
+use serde_json::Value;
+
+use crate::{BufReadExt, FormattedRead};
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+impl<R: BufRead> FormattedRead<R> {
+    /// The text of the next JSON value. Objects and arrays are read up to
+    /// their matching closing bracket, taking strings and their escapes
+    /// into account; numbers and the literals `true`, `false`, `null` end
+    /// before the next byte that cannot be part of them. Nothing after the
+    /// value is consumed.
+    fn scan_json(&mut self) -> io::Result<Vec<u8>> {
+        self.skip_delims()?;
+        let first = self.r.fill_buf_nonempty()?[0];
+        let mut res = vec![];
+        if first == b'{' || first == b'[' || first == b'"' {
+            let mut open = vec![];
+            let mut in_string = false;
+            let mut escaped = false;
+            loop {
+                let buf = self.r.fill_buf_nonempty()?;
+                let mut end = None;
+                for (i, &c) in buf.iter().enumerate() {
+                    if in_string {
+                        if escaped {
+                            escaped = false;
+                        } else if c == b'\\' {
+                            escaped = true;
+                        } else if c == b'"' {
+                            in_string = false;
+                        }
+                    } else {
+                        match c {
+                            b'"' => in_string = true,
+                            b'{' => open.push(b'}'),
+                            b'[' => open.push(b']'),
+                            b'}' | b']' if open.pop() != Some(c) => return Err(invalid("mismatched bracket")),
+                            _ => {}
+                        }
+                    }
+                    if open.is_empty() && !in_string {
+                        end = Some(i+1);
+                        break;
+                    }
+                }
+                let l = end.unwrap_or(buf.len());
+                res.extend_from_slice(&buf[..l]);
+                self.r.consume(l);
+                if end.is_some() {
+                    break;
+                }
+            }
+        } else {
+            let is_scalar = |c: u8| c.is_ascii_alphanumeric() || c == b'-' || c == b'+' || c == b'.';
+            loop {
+                let buf = self.r.fill_buf()?;
+                let l = buf.iter().position(|&c| !is_scalar(c)).unwrap_or(buf.len());
+                let done = l < buf.len() || buf.is_empty();
+                res.extend_from_slice(&buf[..l]);
+                self.r.consume(l);
+                if done {
+                    break;
+                }
+            }
+            if res.is_empty() {
+                return Err(invalid("expected a JSON value"));
+            }
+        }
+        Ok(res)
+    }
+
+    /// Read one JSON value, which may span lines, see `scan_json` for where
+    /// it ends.
+    pub fn read_json(&mut self) -> io::Result<Value> {
+        let text = self.scan_json()?;
+        let value = serde_json::from_slice(&text).map_err(|e| invalid(&format!("invalid JSON value: {}", e)));
+        if value.is_ok() {
+            self.end_token(None);
+        }
+        self.count(value)
+    }
+}
+
+#[test]
+fn test_json() {
+    use serde_json::json;
+
+    let input = br#"2 {"a": [1, {"b": "x}\"]"}],
+ "c": null} [true,false]-1.5e3 "s" 7"#;
+    let mut reader = FormattedRead::new(std::io::BufReader::with_capacity(5, &input[..]));
+    input! { reader => n: u32, a, b, c, d: json, m: u32 }
+    assert_eq!(n, 2);
+    assert_eq!(a, json!({"a": [1, {"b": "x}\"]"}], "c": null}));
+    assert_eq!(b, json!([true, false]));
+    assert_eq!(c, json!(-1.5e3));
+    assert_eq!(d, json!("s"));
+    assert_eq!(m, 7);
+    assert!(FormattedRead::new(&b"[1}"[..]).read_json().is_err());
+    assert!(FormattedRead::new(&b"{\"a\": 1"[..]).read_json().is_err());
+    assert!(FormattedRead::new(&b"[1,,2]"[..]).read_json().is_err());
+}
+
+#[test]
+fn test_json_next_token() {
+    // the token right after the value is left for the next read, also
+    // without whitespace in between
+    let mut reader = FormattedRead::new(&b"{\"k\": 1}x [2]3\nnull;"[..]);
+    assert_eq!(reader.read_json().unwrap(), serde_json::json!({"k": 1}));
+    assert_eq!(reader.read_word::<char>().unwrap(), 'x');
+    assert_eq!(reader.read_json().unwrap(), serde_json::json!([2]));
+    assert_eq!(reader.read_word::<u8>().unwrap(), 3);
+    assert_eq!(reader.read_json().unwrap(), serde_json::Value::Null);
+    assert_eq!(reader.read_word::<char>().unwrap(), ';');
+}
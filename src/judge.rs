@@ -0,0 +1,186 @@
+//! Running a solution under time and memory limits, with the verdicts of
+//! a judge.
+
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::tokens_match;
+
+/// The result of running a solution on one test.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Accepted,
+    WrongAnswer,
+    TimeLimit,
+    MemoryLimit,
+    /// The solution exited unsuccessfully, with the given status.
+    RuntimeError(String),
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Accepted => write!(f, "accepted"),
+            Verdict::WrongAnswer => write!(f, "wrong answer"),
+            Verdict::TimeLimit => write!(f, "time limit exceeded"),
+            Verdict::MemoryLimit => write!(f, "memory limit exceeded"),
+            Verdict::RuntimeError(status) => write!(f, "runtime error ({})", status),
+        }
+    }
+}
+
+/// The limits of a test. `memory` is in bytes of resident memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    pub time: Duration,
+    pub memory: Option<u64>,
+}
+
+impl Limits {
+    pub fn new(time: Duration) -> Self {
+        Limits { time, memory: None }
+    }
+
+    pub fn with_memory(self, bytes: u64) -> Self {
+        Limits { memory: Some(bytes), ..self }
+    }
+}
+
+/// A verdict together with the running time and output it is based on.
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    pub verdict: Verdict,
+    pub time: Duration,
+    pub output: Vec<u8>,
+}
+
+/// The peak resident memory of the process `pid` in bytes, on Linux.
+fn peak_rss(pid: u32) -> Option<u64> {
+    let status = std::fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+    let line = status.lines().find(|l| l.starts_with("VmHWM:"))?;
+    let kb: u64 = line[6..].trim().trim_end_matches("kB").trim().parse().ok()?;
+    Some(kb * 1024)
+}
+
+/// Kill what is left of the process group `pgid`.
+#[cfg(all(unix, feature = "libc", not(feature = "bundle")))]
+fn kill_group(pgid: u32) {
+    // SAFETY: `kill` only sends a signal, a missing group is reported
+    // through the result
+    unsafe {
+        libc::kill(-(pgid as libc::pid_t), libc::SIGKILL);
+    }
+}
+
+/// Kill what is left of the process group `pgid`.
+#[cfg(all(unix, any(not(feature = "libc"), feature = "bundle")))]
+fn kill_group(pgid: u32) {
+    let _ = Command::new("kill")
+        .args(["-KILL", "--", &format!("-{}", pgid)])
+        .stderr(Stdio::null())
+        .status();
+}
+
+/// Run `cmd` with `input` as its standard input and judge its output
+/// against `expected` with `tokens_match`. The process is killed when it
+/// exceeds the time limit, or the memory limit, which is only checked on
+/// Linux by polling its peak resident memory. On Unix the process runs in
+/// its own process group, which is killed as a whole, so that processes
+/// it started do not outlive it.
+///
+/// This sets the standard streams of `cmd` and, on Unix, its process
+/// group, so `cmd` can be judged again on the next test but no longer be
+/// spawned in the caller's process group.
+pub fn judge_command(cmd: &mut Command, input: &[u8], expected: &[u8], limits: &Limits) -> io::Result<Outcome> {
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(cmd, 0);
+    let start = Instant::now();
+    let mut child = cmd.stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::null()).spawn()?;
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let input = input.to_vec();
+    // a solution may exit without reading all of its input
+    let writer = thread::spawn(move || drop(stdin.write_all(&input)));
+    let mut stdout = child.stdout.take().expect("stdout is piped");
+    let output = Arc::new(Mutex::new(vec![]));
+    let reader = {
+        let output = Arc::clone(&output);
+        thread::spawn(move || {
+            let mut buf = [0; 1 << 13];
+            while let Ok(n @ 1..) = stdout.read(&mut buf) {
+                output.lock().unwrap().extend_from_slice(&buf[..n]);
+            }
+        })
+    };
+
+    let mut peak = 0;
+    let over_memory = |peak| limits.memory.is_some_and(|m| peak > m);
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break Some(status);
+        }
+        peak = peak.max(peak_rss(child.id()).unwrap_or(0));
+        if over_memory(peak) || start.elapsed() > limits.time {
+            child.kill()?;
+            child.wait()?;
+            break None;
+        }
+        thread::sleep(Duration::from_millis(1));
+    };
+    let time = start.elapsed();
+    #[cfg(unix)]
+    kill_group(child.id());
+    // with the whole group killed the pipes are closed, but processes left
+    // after a kill on other platforms may keep them open, so there only
+    // the output read so far is used
+    if cfg!(unix) || status.is_some() {
+        let _ = writer.join();
+        let _ = reader.join();
+    }
+    let output = std::mem::take(&mut *output.lock().unwrap());
+
+    let verdict = match status {
+        None if over_memory(peak) => Verdict::MemoryLimit,
+        None => Verdict::TimeLimit,
+        Some(status) if !status.success() => Verdict::RuntimeError(status.to_string()),
+        Some(_) if !tokens_match(expected, &output) => Verdict::WrongAnswer,
+        Some(_) => Verdict::Accepted,
+    };
+    Ok(Outcome { verdict, time, output })
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_judge_command() {
+    let limits = Limits::new(Duration::from_secs(2)).with_memory(16 << 20);
+    let judge = |script: &str, input: &[u8], expected: &[u8]| {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(script);
+        judge_command(&mut cmd, input, expected, &limits).unwrap()
+    };
+
+    let sum = "read a b; echo $((a + b))";
+    assert_eq!(judge(sum, b"1 2\n", b"3").verdict, Verdict::Accepted);
+    assert_eq!(judge(sum, b"1 2\n", b"4").verdict, Verdict::WrongAnswer);
+    assert!(matches!(judge("exit 3", b"", b"").verdict, Verdict::RuntimeError(_)));
+
+    let outcome = judge("while :; do :; done", b"", b"");
+    assert_eq!(outcome.verdict, Verdict::TimeLimit);
+    assert!(outcome.time >= limits.time);
+
+    // the shell keeps the 32 MiB in a variable
+    let hog = "x=$(head -c 33554432 /dev/zero | tr '\\0' a); sleep 30";
+    let start = Instant::now();
+    assert_eq!(judge(hog, b"", b"").verdict, Verdict::MemoryLimit);
+    assert!(start.elapsed() < Duration::from_secs(10));
+
+    // the child holding the output pipe is killed with the shell
+    let start = Instant::now();
+    let outcome = judge("echo partial; sleep 30; echo done", b"", b"");
+    assert_eq!(outcome.verdict, Verdict::TimeLimit);
+    assert_eq!(outcome.output, b"partial\n");
+    assert!(start.elapsed() < Duration::from_secs(10));
+}
@@ -2,8 +2,7 @@
 //!
 //! # Example: reading a graph given as an edge list
 //! ```rust,no_run
-//! #[macro_use]
-//! extern crate comp_input;
+//! use comp_input::prelude::*;
 //!
 //! fn main() {
 //!     input! {
@@ -16,8 +15,12 @@
 use std::str::FromStr;
 use std::io::BufRead;
 
-extern crate memchr;
-use memchr::{memchr};
+#[cfg(all(feature = "memchr", not(feature = "bundle")))]
+use memchr::{memchr, memchr2};
+#[cfg(any(not(feature = "memchr"), feature = "bundle"))]
+use byte_search::{memchr, memchr2};
+
+use counted::Counted;
 
 trait BufReadExt : BufRead {
     #[inline]
@@ -35,6 +38,126 @@ impl<R: BufRead> BufReadExt for R {}
 
 pub trait FromAscii : Sized {
     fn from_ascii(src: &[u8]) -> Option<Self>;
+
+    /// Parse `src` according to the number format set on the reader. Only
+    /// number types need to override this.
+    #[inline]
+    fn from_ascii_fmt(src: &[u8], fmt: &NumberFormat) -> Option<Self> {
+        let _ = fmt;
+        Self::from_ascii(src)
+    }
+
+    /// The error for a word that `from_ascii` rejected.
+    #[inline]
+    fn parse_error(src: &[u8]) -> std::io::Error {
+        let _ = src;
+        std::io::ErrorKind::InvalidData.into()
+    }
+}
+
+/// Reader settings for the notation of numbers in the input, passed to
+/// `FromAscii::from_ascii_fmt`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct NumberFormat {
+    /// Floats use `,` instead of `.` as decimal separator, as in `3,14`.
+    pub decimal_comma: bool,
+    /// Floats may have an exponent, as in `1e9`.
+    pub allow_exponent: bool,
+    /// Floats may be infinite or `NaN`, as in `inf` or `nan`.
+    pub allow_inf_nan: bool,
+    /// Integers may contain this byte between digits, as in `1_000_000`.
+    pub digit_separator: Option<u8>,
+    /// How unsigned integers treat a leading `-`.
+    pub negative_unsigned: NegativeUnsigned,
+}
+
+/// How a negative value such as `-5` is read into an unsigned integer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum NegativeUnsigned {
+    /// Fail with `ParseError::NegativeUnsigned`.
+    #[default]
+    Reject,
+    /// Read it as 0.
+    Saturate,
+    /// Read it as its absolute value.
+    Abs,
+}
+
+/// Errors for words that are well-formed but cannot be read into the
+/// requested type. They are returned wrapped in an `std::io::Error` of
+/// kind `InvalidData`, see `std::io::Error::get_ref`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseError {
+    /// A word with a leading `-` was read into an unsigned integer.
+    NegativeUnsigned(String),
+    /// A word with value 0 was read as a 1-based index (`usize1`).
+    ZeroIndex(String),
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ParseError::NegativeUnsigned(token) => write!(f, "negative value for unsigned type: {}", token),
+            ParseError::ZeroIndex(token) => write!(f, "zero for 1-based index: {}", token),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        NumberFormat {
+            decimal_comma: false,
+            allow_exponent: true,
+            allow_inf_nan: true,
+            digit_separator: None,
+            negative_unsigned: NegativeUnsigned::Reject,
+        }
+    }
+}
+
+/// Types that are read from one or more consecutive words.
+///
+/// Every `FromAscii` type is read from a single word. Implement this trait
+/// directly for types made of several words, such as points.
+pub trait FromTokens : Sized {
+    fn from_tokens<R: BufRead>(r: &mut FormattedRead<R>) -> std::io::Result<Self>;
+}
+
+impl<T: FromAscii> FromTokens for T {
+    #[inline]
+    fn from_tokens<R: BufRead>(r: &mut FormattedRead<R>) -> std::io::Result<T> {
+        r.read_word()
+    }
+}
+
+/// Remove the separators from an integer such as `-1,000,000`. Each one
+/// must be between two digits.
+fn strip_digit_separator(src: &[u8], sep: u8) -> Option<Vec<u8>> {
+    let mut res = Vec::with_capacity(src.len());
+    for (i, &c) in src.iter().enumerate() {
+        if c == sep {
+            let digit_before = i > 0 && src[i-1].is_ascii_digit();
+            let digit_after = src.get(i+1).is_some_and(u8::is_ascii_digit);
+            if !digit_before || !digit_after {
+                return None;
+            }
+        } else {
+            res.push(c);
+        }
+    }
+    Some(res)
+}
+
+#[inline]
+fn from_ascii_sep<T: FromAscii>(src: &[u8], sep: Option<u8>) -> Option<T> {
+    match sep {
+        Some(sep) if src.contains(&sep) => T::from_ascii(&strip_digit_separator(src, sep)?),
+        _ => T::from_ascii(src),
+    }
 }
 
 macro_rules! from_ascii_int_impl {
@@ -68,6 +191,11 @@ macro_rules! from_ascii_int_impl {
                         Some(res)
                     }
                 }
+
+                #[inline]
+                fn from_ascii_fmt(src: &[u8], fmt: &NumberFormat) -> Option<$t> {
+                    from_ascii_sep(src, fmt.digit_separator)
+                }
             }
         )*
     }
@@ -93,6 +221,27 @@ macro_rules! from_ascii_uint_impl {
 
                     Some(res)
                 }
+
+                #[inline]
+                fn from_ascii_fmt(src: &[u8], fmt: &NumberFormat) -> Option<$t> {
+                    match (src.split_first(), fmt.negative_unsigned) {
+                        (Some((b'-', _)), NegativeUnsigned::Reject) | (None, _) => None,
+                        (Some((b'-', digits)), mode) => {
+                            let x: $t = from_ascii_sep(digits, fmt.digit_separator)?;
+                            Some(if mode == NegativeUnsigned::Saturate { 0 } else { x })
+                        }
+                        _ => from_ascii_sep(src, fmt.digit_separator),
+                    }
+                }
+
+                fn parse_error(src: &[u8]) -> std::io::Error {
+                    if src.len() > 1 && src[0] == b'-' {
+                        let token = String::from_utf8_lossy(src).into_owned();
+                        std::io::Error::new(std::io::ErrorKind::InvalidData, ParseError::NegativeUnsigned(token))
+                    } else {
+                        std::io::ErrorKind::InvalidData.into()
+                    }
+                }
             }
         )*
     }
@@ -100,13 +249,129 @@ macro_rules! from_ascii_uint_impl {
 from_ascii_uint_impl! { u8 u16 u32 u64 usize }
 from_ascii_int_impl!  { i8 i16 i32 i64 isize }
 
+/// Integers that can be read with values outside of their range clamped
+/// to the nearest bound, see `FormattedRead::read_saturating`.
+pub trait SaturatingInt : FromAscii {
+    fn from_ascii_saturating(src: &[u8], fmt: &NumberFormat) -> Option<Self>;
+}
+
+macro_rules! saturating_int_impl {
+    ($($t:ty)*) => {
+        $(
+            impl SaturatingInt for $t {
+                fn from_ascii_saturating(src: &[u8], fmt: &NumberFormat) -> Option<$t> {
+                    let stripped;
+                    let src = match fmt.digit_separator {
+                        Some(sep) if src.contains(&sep) => {
+                            stripped = strip_digit_separator(src, sep)?;
+                            &stripped[..]
+                        }
+                        _ => src,
+                    };
+                    let (sign, digits) = match src.split_first() {
+                        Some((&c, rest)) if c == b'+' || c == b'-' => (c == b'-', rest),
+                        _ => (false, src),
+                    };
+                    if digits.is_empty() {
+                        return None;
+                    }
+                    let mut res: i128 = 0;
+                    for &c in digits {
+                        let x = (c as char).to_digit(10)? as i128;
+                        res = res.saturating_mul(10).saturating_add(x);
+                    }
+                    if sign {
+                        res = -res;
+                    }
+                    Some(res.clamp(<$t>::MIN as i128, <$t>::MAX as i128) as $t)
+                }
+            }
+        )*
+    }
+}
+saturating_int_impl! { u8 u16 u32 u64 usize i8 i16 i32 i64 isize }
+
+macro_rules! from_ascii_float_impl {
+    ($($t:ty)*) => {
+        $(
+            impl FromAscii for $t {
+                #[inline]
+                fn from_ascii(src: &[u8]) -> Option<$t> {
+                    std::str::from_utf8(src).ok()?.parse().ok()
+                }
+
+                fn from_ascii_fmt(src: &[u8], fmt: &NumberFormat) -> Option<$t> {
+                    if !fmt.allow_exponent && src.iter().any(|&c| c == b'e' || c == b'E') {
+                        return None;
+                    }
+                    let x = if fmt.decimal_comma && src.contains(&b',') {
+                        let src: Vec<u8> = src.iter().map(|&c| if c == b',' { b'.' } else { c }).collect();
+                        Self::from_ascii(&src)?
+                    } else {
+                        Self::from_ascii(src)?
+                    };
+                    if !fmt.allow_inf_nan && !x.is_finite() {
+                        return None;
+                    }
+                    Some(x)
+                }
+            }
+        )*
+    }
+}
+from_ascii_float_impl! { f32 f64 }
+
+impl<T: FromAscii> FromAscii for std::num::Wrapping<T> {
+    #[inline]
+    fn from_ascii(src: &[u8]) -> Option<Self> {
+        T::from_ascii(src).map(std::num::Wrapping)
+    }
+
+    #[inline]
+    fn from_ascii_fmt(src: &[u8], fmt: &NumberFormat) -> Option<Self> {
+        T::from_ascii_fmt(src, fmt).map(std::num::Wrapping)
+    }
+}
+
+impl<T: FromAscii> FromAscii for std::num::Saturating<T> {
+    #[inline]
+    fn from_ascii(src: &[u8]) -> Option<Self> {
+        T::from_ascii(src).map(std::num::Saturating)
+    }
+
+    #[inline]
+    fn from_ascii_fmt(src: &[u8], fmt: &NumberFormat) -> Option<Self> {
+        T::from_ascii_fmt(src, fmt).map(std::num::Saturating)
+    }
+}
+
+/// A single byte, or a single UTF-8 encoded character.
 impl FromAscii for char {
     #[inline]
     fn from_ascii(src: &[u8]) -> Option<char> {
+        if src.len() == 1 {
+            return Some(src[0] as char)
+        }
+        let mut chars = std::str::from_utf8(src).ok()?.chars();
+        let c = chars.next()?;
+        if chars.next().is_some() {
+            return None
+        }
+        Some(c)
+    }
+}
+
+/// A word consisting of a single byte.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Byte(pub u8);
+
+impl FromAscii for Byte {
+    #[inline]
+    fn from_ascii(src: &[u8]) -> Option<Byte> {
         if src.len() != 1 {
             return None
         }
-        Some(src[0] as char)
+        Some(Byte(src[0]))
     }
 }
 
@@ -118,15 +383,98 @@ impl FromAscii for String {
 }
 
 
+/// Set of bytes separating the tokens returned by `read_word`.
+#[derive(Clone)]
+struct Delimiters([bool; 256]);
+
+impl Delimiters {
+    fn new(bytes: &[u8]) -> Self {
+        let mut set = [false; 256];
+        for &c in bytes {
+            set[c as usize] = true;
+        }
+        Delimiters(set)
+    }
+
+    fn whitespace() -> Self {
+        Delimiters::new(b" \t\n\x0C\r")
+    }
+
+    #[inline]
+    fn contains(&self, c: u8) -> bool {
+        self.0[c as usize]
+    }
+}
+
+/// How strictly `read_word` and `read_line` treat the whitespace around
+/// tokens.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Whitespace {
+    /// Any run of delimiters separates two words (the default).
+    #[default]
+    Lenient,
+    /// Words are separated by exactly one delimiter, and lines have no
+    /// leading or trailing spaces.
+    SingleSpace,
+    /// Like `SingleSpace`, but the only whitespace allowed between words is
+    /// `' '` and lines must end with a plain `\n`.
+    Exact,
+}
+
+impl Whitespace {
+    /// Check the delimiter ending a word.
+    #[inline]
+    fn check_delim(self, delim: u8) -> std::io::Result<()> {
+        if self == Whitespace::Exact && delim.is_ascii_whitespace() && delim != b' ' && delim != b'\n' {
+            return Err(unexpected_whitespace());
+        }
+        Ok(())
+    }
+
+    /// Check a line read by `read_line` for leading and trailing spaces.
+    fn check_line(self, line: &[u8]) -> std::io::Result<()> {
+        let is_space = |c: &u8| *c == b' ' || *c == b'\t';
+        if self != Whitespace::Lenient && (line.first().is_some_and(is_space) || line.last().is_some_and(is_space)) {
+            return Err(unexpected_whitespace());
+        }
+        Ok(())
+    }
+}
+
+/// The settings of a `FormattedRead`, to read another source the same way.
+/// See `FormattedRead::config`.
+#[derive(Clone)]
+pub struct ReadConfig {
+    delims: Delimiters,
+    keep_indent: bool,
+    unicode: bool,
+    cr_newline: bool,
+    max_token_len: usize,
+    max_alloc: usize,
+    whitespace: Whitespace,
+    numbers: NumberFormat,
+}
+
 pub struct FormattedRead<R: BufRead> {
-    r: R,
+    r: Counted<R>,
+    tokens: u64,
     buf: Vec<u8>,
+    delims: Delimiters,
+    keep_indent: bool,
+    unicode: bool,
+    cr_newline: bool,
+    max_token_len: usize,
+    max_alloc: usize,
+    at_bol: bool,
+    eol_pending: bool,
+    whitespace: Whitespace,
+    numbers: NumberFormat,
 }
 
-fn consume_ws<R: BufRead>(r: &mut R) -> std::io::Result<()> {
+fn consume_while<R: BufRead, P: Fn(u8) -> bool>(r: &mut R, pred: P) -> std::io::Result<()> {
     loop {
         let buf = r.fill_buf_nonempty()?;
-        if let Some(ix) = buf.iter().position(|&c| !c.is_ascii_whitespace()) {
+        if let Some(ix) = buf.iter().position(|&c| !pred(c)) {
             r.consume(ix);
             return Ok(());
         } else {
@@ -136,21 +484,411 @@ fn consume_ws<R: BufRead>(r: &mut R) -> std::io::Result<()> {
     }
 }
 
+fn consume_ws<R: BufRead>(r: &mut R) -> std::io::Result<()> {
+    consume_while(r, |c| c.is_ascii_whitespace())
+}
+
+fn unexpected_whitespace() -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, "unexpected whitespace")
+}
+
+fn token_too_long() -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, "token exceeds maximum length")
+}
+
+/// Default for `FormattedRead::with_max_alloc`, 256 MiB.
+pub const DEFAULT_MAX_ALLOC: usize = 256 << 20;
+
+/// The number of items `n` as a `usize`, if `n` items of `T` fit into
+/// `max_bytes`.
+fn checked_len<T, N>(max_bytes: usize, n: N) -> std::io::Result<usize>
+where
+    N: std::convert::TryInto<usize> + std::fmt::Display + Copy,
+{
+    let len = n.try_into().map_err(|_| {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, format!("invalid number of items: {}", n))
+    })?;
+    let size = std::mem::size_of::<T>();
+    if len.saturating_mul(size) > max_bytes {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("{} items of {} bytes exceed the allocation limit of {} bytes", len, size, max_bytes),
+        ));
+    }
+    Ok(len)
+}
+
+/// Collect `n` values produced by `f` into a `Vec`, failing up front if it
+/// would take more than `max_bytes`. Used by the `[T; n]` fragment.
+#[doc(hidden)]
+pub fn collect_vec<T, N, F>(max_bytes: usize, n: N, mut f: F) -> std::io::Result<Vec<T>>
+where
+    N: std::convert::TryInto<usize> + std::fmt::Display + Copy,
+    F: FnMut() -> T,
+{
+    let len = checked_len::<T, N>(max_bytes, n)?;
+    Ok((0..len).map(|_| f()).collect())
+}
+
+/// `n` values produced by `f`, failing up front if collecting them would
+/// take more than `max_bytes`. Used by the fragments repeating an item.
+#[doc(hidden)]
+pub fn repeat_checked<T, N, F>(max_bytes: usize, n: N, f: F) -> std::io::Result<impl Iterator<Item = T>>
+where
+    N: std::convert::TryInto<usize> + std::fmt::Display + Copy,
+    F: FnMut() -> T,
+{
+    let len = checked_len::<T, N>(max_bytes, n)?;
+    Ok(std::iter::repeat_with(f).take(len))
+}
+
+/// Like `collect_vec`, but replace the contents of `v`, reusing its
+/// allocation. Used by `input_into!`.
+#[doc(hidden)]
+pub fn refill_vec<T, N, F>(v: &mut Vec<T>, max_bytes: usize, n: N, mut f: F) -> std::io::Result<()>
+where
+    N: std::convert::TryInto<usize> + std::fmt::Display + Copy,
+    F: FnMut() -> T,
+{
+    let len = checked_len::<T, N>(max_bytes, n)?;
+    v.clear();
+    v.extend((0..len).map(|_| f()));
+    Ok(())
+}
+
 impl<R: BufRead> FormattedRead<R> {
     pub fn new(r: R) -> Self {
         FormattedRead {
-            r,
-            buf: vec![]
+            r: Counted::new(r),
+            tokens: 0,
+            buf: vec![],
+            delims: Delimiters::whitespace(),
+            keep_indent: false,
+            unicode: false,
+            cr_newline: false,
+            max_token_len: usize::MAX,
+            max_alloc: DEFAULT_MAX_ALLOC,
+            at_bol: true,
+            eol_pending: false,
+            whitespace: Whitespace::Lenient,
+            numbers: NumberFormat::default(),
+        }
+    }
+
+    /// The settings made with the `with_*` methods.
+    pub fn config(&self) -> ReadConfig {
+        ReadConfig {
+            delims: self.delims.clone(),
+            keep_indent: self.keep_indent,
+            unicode: self.unicode,
+            cr_newline: self.cr_newline,
+            max_token_len: self.max_token_len,
+            max_alloc: self.max_alloc,
+            whitespace: self.whitespace,
+            numbers: self.numbers,
+        }
+    }
+
+    /// Apply all settings of `config`, taken from another reader with
+    /// `config`.
+    pub fn with_config(mut self, config: ReadConfig) -> Self {
+        self.delims = config.delims;
+        self.keep_indent = config.keep_indent;
+        self.unicode = config.unicode;
+        self.cr_newline = config.cr_newline;
+        self.max_token_len = config.max_token_len;
+        self.max_alloc = config.max_alloc;
+        self.whitespace = config.whitespace;
+        self.numbers = config.numbers;
+        self
+    }
+
+    /// The underlying source.
+    pub fn get_ref(&self) -> &R {
+        &self.r.inner
+    }
+
+    /// The underlying source, e.g. to push more input into a `ByteQueue`.
+    /// Bytes it already handed out are not affected.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.r.inner
+    }
+
+    /// Number of words and lines read successfully so far.
+    pub fn tokens_read(&self) -> u64 {
+        self.tokens
+    }
+
+    /// Number of bytes of the input consumed so far.
+    pub fn bytes_consumed(&self) -> u64 {
+        self.r.consumed
+    }
+
+    #[inline]
+    fn count<T>(&mut self, res: std::io::Result<T>) -> std::io::Result<T> {
+        if res.is_ok() {
+            self.tokens += 1;
+        }
+        res
+    }
+
+    /// Use `delims` instead of ASCII whitespace to separate the tokens read
+    /// by `read_word`, e.g. `&[b',', b' ', b'\n']` for comma separated values.
+    pub fn with_delimiters(mut self, delims: &[u8]) -> Self {
+        self.delims = Delimiters::new(delims);
+        self
+    }
+
+    /// If `keep` is set, `read_line` and `read_exact_str` only skip line
+    /// breaks before reading, so that leading spaces of a line are preserved.
+    pub fn with_leading_whitespace(mut self, keep: bool) -> Self {
+        self.keep_indent = keep;
+        self
+    }
+
+    /// If `enable` is set, a `\r` that is not followed by `\n` also ends a
+    /// line, as in old Mac files.
+    pub fn with_cr_newlines(mut self, enable: bool) -> Self {
+        self.cr_newline = enable;
+        self
+    }
+
+    /// Fail with `InvalidData` as soon as a word read by `read_word` is
+    /// longer than `len` bytes, instead of buffering it completely.
+    pub fn with_max_token_len(mut self, len: usize) -> Self {
+        self.max_token_len = len;
+        self
+    }
+
+    /// Fail instead of allocating more than `bytes` for the `n` items of a
+    /// `[T; n]`, `set`, `map`, `columns` or similar fragment, which usually
+    /// means that `n` was read from the wrong place. Defaults to
+    /// `DEFAULT_MAX_ALLOC`.
+    pub fn with_max_alloc(mut self, bytes: usize) -> Self {
+        self.max_alloc = bytes;
+        self
+    }
+
+    /// The allocation limit set with `with_max_alloc`.
+    pub fn max_alloc(&self) -> usize {
+        self.max_alloc
+    }
+
+    /// Set the whitespace policy, see `Whitespace`.
+    pub fn with_whitespace(mut self, policy: Whitespace) -> Self {
+        self.whitespace = policy;
+        self
+    }
+
+    /// Check the input as strictly as possible, e.g. when validating
+    /// generated tests: `Whitespace::Exact`, no byte order mark, and floats
+    /// without an exponent or infinite values.
+    pub fn strict(self) -> Self {
+        self.with_whitespace(Whitespace::Exact)
+            .with_bom_skip(false)
+            .with_float_exponent(false)
+            .with_float_inf_nan(false)
+    }
+
+    /// Whether floats may be written with an exponent, like `1e9`. On by
+    /// default.
+    pub fn with_float_exponent(mut self, allow: bool) -> Self {
+        self.numbers.allow_exponent = allow;
+        self
+    }
+
+    /// Whether floats may be `inf`, `nan` or overflow to infinity. On by
+    /// default.
+    pub fn with_float_inf_nan(mut self, allow: bool) -> Self {
+        self.numbers.allow_inf_nan = allow;
+        self
+    }
+
+    /// Accept `,` instead of `.` as the decimal separator of floats. Should
+    /// not be combined with `,` as a delimiter.
+    pub fn with_decimal_comma(mut self, enable: bool) -> Self {
+        self.numbers.decimal_comma = enable;
+        self
+    }
+
+    /// Let integers contain `sep` between digits, as in `1,000,000` or
+    /// `1_000_000`. The separator should not be a delimiter.
+    pub fn with_digit_separator(mut self, sep: u8) -> Self {
+        self.numbers.digit_separator = Some(sep);
+        self
+    }
+
+    /// Read negative values into unsigned integers according to `mode`
+    /// instead of failing.
+    pub fn with_negative_unsigned(mut self, mode: NegativeUnsigned) -> Self {
+        self.numbers.negative_unsigned = mode;
+        self
+    }
+
+    /// Whether to skip a UTF-8 byte order mark at the start of the input,
+    /// as saved by some Windows editors. On by default.
+    pub fn with_bom_skip(mut self, skip: bool) -> Self {
+        self.r.skip_bom = skip;
+        self
+    }
+
+    /// Skip the delimiters before a word. In the strict whitespace modes the
+    /// previous word must have consumed the only one.
+    fn skip_delims(&mut self) -> std::io::Result<()> {
+        let delims = &self.delims;
+        if self.whitespace == Whitespace::Lenient {
+            return consume_while(&mut self.r, |c| delims.contains(c));
+        }
+        match self.r.fill_buf_nonempty()?[0] {
+            c if delims.contains(c) => Err(unexpected_whitespace()),
+            _ => Ok(()),
+        }
+    }
+
+    fn skip_to_field(&mut self) -> std::io::Result<()> {
+        if self.whitespace != Whitespace::Lenient {
+            Ok(())
+        } else if self.keep_indent {
+            consume_while(&mut self.r, |c| c == b'\n' || c == b'\r')
+        } else {
+            consume_ws(&mut self.r)
+        }
+    }
+
+    #[inline]
+    fn next_byte(&mut self) -> std::io::Result<Option<u8>> {
+        let c = self.r.fill_buf()?.first().copied();
+        if c.is_some() {
+            self.r.consume(1);
         }
+        Ok(c)
+    }
+
+    #[inline]
+    fn peek_byte(&mut self) -> std::io::Result<Option<u8>> {
+        Ok(self.r.fill_buf()?.first().copied())
+    }
+
+    /// Read the next character verbatim, without skipping whitespace.
+    /// In Unicode mode a whole UTF-8 sequence is decoded, otherwise a
+    /// single byte is returned.
+    pub fn read_char_raw(&mut self) -> std::io::Result<char> {
+        let c = if self.unicode {
+            self.read_utf8_char()?
+        } else {
+            self.next_byte()?.map(char::from)
+        };
+        c.ok_or_else(|| std::io::ErrorKind::UnexpectedEof.into())
+    }
+
+    /// Read a value spanning one or more words.
+    pub fn read_value<T: FromTokens>(&mut self) -> std::io::Result<T> {
+        T::from_tokens(self)
     }
 
+    /// Read the next word as a `T`. If it does not parse, the word is left
+    /// in the input, so that it can be read again as a different type.
     pub fn read_word<T: FromAscii>(&mut self) -> std::io::Result<T> {
-        consume_ws(&mut self.r)?;
+        let fmt = self.numbers;
+        self.read_word_limited(self.max_token_len, |w| T::from_ascii_fmt(w, &fmt), T::parse_error)
+    }
+
+    /// Read an integer, clamping values outside of the range of `T` to
+    /// `T::MIN` or `T::MAX` instead of wrapping around.
+    pub fn read_saturating<T: SaturatingInt>(&mut self) -> std::io::Result<T> {
+        let fmt = self.numbers;
+        self.read_word_with(|w| T::from_ascii_saturating(w, &fmt))
+    }
+
+    /// Read a 1-based index and convert it to 0-based, failing with
+    /// `ParseError::ZeroIndex` for 0.
+    pub fn read_usize1(&mut self) -> std::io::Result<usize> {
+        let fmt = self.numbers;
+        let parse = move |w: &[u8]| usize::from_ascii_fmt(w, &fmt);
+        self.read_word_limited(self.max_token_len, |w| parse(w)?.checked_sub(1), |w| match parse(w) {
+            Some(_) => {
+                let token = String::from_utf8_lossy(w).into_owned();
+                std::io::Error::new(std::io::ErrorKind::InvalidData, ParseError::ZeroIndex(token))
+            }
+            None => usize::parse_error(w),
+        })
+    }
+
+    /// Check whether another word follows before the end of the input,
+    /// skipping the delimiters in front of it.
+    pub fn has_next(&mut self) -> std::io::Result<bool> {
+        if self.whitespace != Whitespace::Lenient {
+            return Ok(self.peek_byte()?.is_some());
+        }
+        let delims = &self.delims;
+        match consume_while(&mut self.r, |c| delims.contains(c)) {
+            Err(ref e) if e.kind() == std::io::ErrorKind::UnexpectedEof => Ok(false),
+            res => res.map(|()| true),
+        }
+    }
+
+    /// Like `read_word`, but returns `None` if only delimiters remain before
+    /// the end of the input.
+    pub fn read_word_opt<T: FromAscii>(&mut self) -> std::io::Result<Option<T>> {
+        match self.skip_delims() {
+            Err(ref e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+            res => res?,
+        }
+        match self.read_word() {
+            // only Unicode whitespace was left
+            Err(ref e) if self.unicode && e.kind() == std::io::ErrorKind::UnexpectedEof => Ok(None),
+            res => res.map(Some),
+        }
+    }
+
+    /// Like `read_word`, but fail if the word is longer than `limit` bytes.
+    pub fn read_word_max_len<T: FromAscii>(&mut self, limit: usize) -> std::io::Result<T> {
+        let limit = limit.min(self.max_token_len);
+        let fmt = self.numbers;
+        self.read_word_limited(limit, |w| T::from_ascii_fmt(w, &fmt), T::parse_error)
+    }
+
+    /// Like `read_word`, but parse the word using `f`.
+    pub fn read_word_with<T, F: FnOnce(&[u8]) -> Option<T>>(&mut self, f: F) -> std::io::Result<T> {
+        self.read_word_limited(self.max_token_len, f, |_| std::io::ErrorKind::InvalidData.into())
+    }
+
+    /// Read a word of at most `limit` bytes and parse it using `f`. If that
+    /// fails, `err` gives the error for the word.
+    fn read_word_limited<T, F, E>(&mut self, limit: usize, f: F, err: E) -> std::io::Result<T>
+    where
+        F: FnOnce(&[u8]) -> Option<T>,
+        E: FnOnce(&[u8]) -> std::io::Error,
+    {
+        let res = if self.unicode {
+            self.read_word_unicode(limit, f, err)
+        } else {
+            self.read_word_ascii(limit, f, err)
+        };
+        self.count(res)
+    }
+
+    fn read_word_ascii<T, F, E>(&mut self, limit: usize, f: F, err: E) -> std::io::Result<T>
+    where
+        F: FnOnce(&[u8]) -> Option<T>,
+        E: FnOnce(&[u8]) -> std::io::Error,
+    {
+        self.skip_delims()?;
+        let delims = &self.delims;
+        let whitespace = self.whitespace;
         let buf = self.r.fill_buf_nonempty()?;
-        let split_ix = buf.iter().position(u8::is_ascii_whitespace);
+        let split_ix = buf.iter().position(|&c| delims.contains(c));
+        if split_ix.unwrap_or(buf.len()) > limit {
+            return Err(token_too_long());
+        }
         if let Some(ix) = split_ix {
-            let res = T::from_ascii(&buf[..ix]).ok_or(std::io::ErrorKind::InvalidData)?;
+            let res = f(&buf[..ix]).ok_or_else(|| err(&buf[..ix]))?;
+            let delim = buf[ix];
+            whitespace.check_delim(delim)?;
             self.r.consume(ix+1);
+            self.consume_lf_after(delim)?;
+            self.check_trailing(delim)?;
+            self.end_token(Some(delim));
             return Ok(res);
         }
 
@@ -160,95 +898,458 @@ impl<R: BufRead> FormattedRead<R> {
         self.r.consume(l);
 
         loop {
-            let buf = self.r.fill_buf_nonempty()?;
-            if let Some(ix) = buf.iter().position(u8::is_ascii_whitespace) {
+            let buf = self.r.fill_buf()?;
+            if buf.is_empty() {
+                // last token of the input, not followed by a delimiter
+                return match f(&self.buf) {
+                    Some(res) => {
+                        self.end_token(None);
+                        Ok(res)
+                    }
+                    None => {
+                        self.r.unread(&self.buf);
+                        Err(err(&self.buf))
+                    }
+                };
+            }
+            let split_ix = buf.iter().position(|&c| delims.contains(c));
+            if self.buf.len() + split_ix.unwrap_or(buf.len()) > limit {
+                return Err(token_too_long());
+            }
+            if let Some(ix) = split_ix {
                 self.buf.extend_from_slice(&buf[..ix]);
-                let res = T::from_ascii(&self.buf).ok_or(std::io::ErrorKind::InvalidData)?;
-                self.r.consume(ix+1); // maybe more?
+                let res = match f(&self.buf) {
+                    Some(res) => res,
+                    None => {
+                        let e = err(&self.buf);
+                        // the part in the current chunk is not consumed yet
+                        let read = self.buf.len() - ix;
+                        self.r.unread(&self.buf[..read]);
+                        return Err(e);
+                    }
+                };
+                let delim = buf[ix];
+                whitespace.check_delim(delim)?;
+                self.r.consume(ix+1);
+                self.consume_lf_after(delim)?;
+                self.check_trailing(delim)?;
+                self.end_token(Some(delim));
                 return Ok(res);
             } else {
-                self.buf.extend_from_slice(&buf);
+                self.buf.extend_from_slice(buf);
                 let l = buf.len();
                 self.r.consume(l);
             }
         }
     }
 
-    pub fn read_line<T: FromStr>(&mut self) -> std::io::Result<T> {
-        consume_ws(&mut self.r)?;
-        let buf = self.r.fill_buf_nonempty()?;
-        if let Some(ix) = memchr(b'\n', buf) {
-            // CR-LF
-            let split = ix.checked_sub(1).filter(|&i| buf[i] == b'\r').unwrap_or(ix);
-            let res = std::str::from_utf8(&buf[..split]).map_err(|_| std::io::ErrorKind::InvalidData)?;
-            let res = res.parse().map_err(|_| std::io::ErrorKind::InvalidData)?;
-            self.r.consume(ix+1); // maybe more?
-            return Ok(res);
+    /// A word terminated by the `\r` of a CR-LF ends at the `\n`, so that a
+    /// following `read_raw_line` starts on the next line.
+    #[inline]
+    fn consume_lf_after(&mut self, delim: u8) -> std::io::Result<()> {
+        if delim == b'\r' && self.peek_byte()? == Some(b'\n') {
+            self.r.consume(1);
         }
-        self.buf.clear();
-        self.buf.extend_from_slice(buf);
-        let l = buf.len();
-        self.r.consume(l);
+        Ok(())
+    }
 
-        loop {
-            let buf = self.r.fill_buf_nonempty()?;
-            if let Some(ix) = memchr(b'\n', buf) {
-                self.buf.extend_from_slice(&buf[..ix]);
-                if self.buf[self.buf.len()-1] == b'\r' {
-                    self.buf.pop();
-                }
+    /// In the strict whitespace modes, a word ending at a delimiter other
+    /// than a line break must be followed by another word, not by the end
+    /// of the line or of the input.
+    fn check_trailing(&mut self, delim: u8) -> std::io::Result<()> {
+        if self.whitespace != Whitespace::Lenient && delim != b'\n' && delim != b'\r' {
+            if let None | Some(b'\n') | Some(b'\r') = self.peek_byte()? {
+                return Err(unexpected_whitespace());
+            }
+        }
+        Ok(())
+    }
 
-                let res = std::str::from_utf8(&self.buf).map_err(|_| std::io::ErrorKind::InvalidData)?;
-                let res = res.parse().map_err(|_| std::io::ErrorKind::InvalidData)?;
-                self.r.consume(ix+1); // maybe more?
+    /// Record whether the last word or line read ended with a line break.
+    #[inline]
+    fn end_token(&mut self, delim: Option<u8>) {
+        self.at_bol = delim == Some(b'\n') || delim == Some(b'\r');
+        self.eol_pending = self.at_bol;
+    }
+
+    /// Check that the reader is at the end of a line: either the last word
+    /// was terminated by a line break, or the next byte is one or the input
+    /// ends. A pending line break is consumed, so two calls in a row
+    /// require an empty line.
+    pub fn expect_eol(&mut self) -> std::io::Result<()> {
+        if self.eol_pending {
+            self.eol_pending = false;
+            return Ok(());
+        }
+        match self.peek_byte()? {
+            None => Ok(()),
+            Some(c) if c == b'\n' || c == b'\r' => {
+                self.r.consume(1);
+                self.consume_lf_after(c)?;
+                self.at_bol = true;
+                Ok(())
+            }
+            Some(_) => Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "expected end of line")),
+        }
+    }
+
+    /// Check that the reader is at the start of a line, i.e. at the start
+    /// of the input or right after a line break.
+    pub fn expect_bol(&mut self) -> std::io::Result<()> {
+        if self.at_bol {
+            Ok(())
+        } else {
+            Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "expected start of line"))
+        }
+    }
+
+    /// Read the current line into `self.buf`, without its terminator, which
+    /// is `\n`, `\r\n` or, if enabled, a lone `\r`. Returns `false` if the
+    /// input is already at its end.
+    fn line_into_buf(&mut self) -> std::io::Result<bool> {
+        self.buf.clear();
+        let mut any = false;
+        loop {
+            let buf = self.r.fill_buf()?;
+            if buf.is_empty() {
+                return Ok(any);
+            }
+            any = true;
+            match memchr2(b'\n', b'\r', buf) {
+                None => {
+                    self.buf.extend_from_slice(buf);
+                    let l = buf.len();
+                    self.r.consume(l);
+                }
+                Some(ix) => {
+                    let c = buf[ix];
+                    self.buf.extend_from_slice(&buf[..ix]);
+                    self.r.consume(ix+1);
+                    if c == b'\n' {
+                        return Ok(true);
+                    }
+                    if self.whitespace == Whitespace::Exact {
+                        return Err(unexpected_whitespace());
+                    }
+                    // the \n of a CR-LF may be in the next chunk
+                    match self.peek_byte()? {
+                        Some(b'\n') => {
+                            self.r.consume(1);
+                            return Ok(true);
+                        }
+                        None => return Ok(true),
+                        Some(_) if self.cr_newline => return Ok(true),
+                        Some(_) => self.buf.push(b'\r'),
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn read_line<T: FromStr>(&mut self) -> std::io::Result<T> {
+        self.read_line_with(|line| std::str::from_utf8(line).ok()?.parse().ok())
+    }
+
+    /// Like `read_line`, but return the bytes of the line without checking
+    /// that they are valid UTF-8.
+    pub fn read_line_bytes(&mut self) -> std::io::Result<Vec<u8>> {
+        self.read_line_with(|line| Some(line.to_vec()))
+    }
+
+    fn read_line_with<T, F: FnOnce(&[u8]) -> Option<T>>(&mut self, f: F) -> std::io::Result<T> {
+        let res = self.read_line_uncounted(f);
+        if res.is_ok() {
+            self.end_token(Some(b'\n'));
+        }
+        self.count(res)
+    }
+
+    fn read_line_uncounted<T, F: FnOnce(&[u8]) -> Option<T>>(&mut self, f: F) -> std::io::Result<T> {
+        self.skip_to_field()?;
+        let whitespace = self.whitespace;
+        let buf = self.r.fill_buf_nonempty()?;
+        if let Some(ix) = memchr2(b'\n', b'\r', buf) {
+            // consume the line including its terminator, if it is known to
+            // be complete
+            let end = match buf.get(ix+1) {
+                _ if buf[ix] == b'\n' => Some(ix+1),
+                _ if whitespace == Whitespace::Exact => return Err(unexpected_whitespace()),
+                Some(b'\n') => Some(ix+2),
+                Some(_) if self.cr_newline => Some(ix+1),
+                _ => None,
+            };
+            if let Some(end) = end {
+                whitespace.check_line(&buf[..ix])?;
+                let res = f(&buf[..ix]).ok_or(std::io::ErrorKind::InvalidData)?;
+                self.r.consume(end);
                 return Ok(res);
-            } else {
-                self.buf.extend_from_slice(&buf);
-                let l = buf.len();
-                self.r.consume(l);
             }
         }
+
+        self.line_into_buf()?;
+        whitespace.check_line(&self.buf)?;
+        f(&self.buf).ok_or_else(|| std::io::ErrorKind::InvalidData.into())
+    }
+
+    /// Read the rest of the current line verbatim, including leading and
+    /// trailing spaces. Returns an empty string for an empty line.
+    pub fn read_raw_line(&mut self) -> std::io::Result<String> {
+        if !self.line_into_buf()? {
+            return Err(std::io::ErrorKind::UnexpectedEof.into());
+        }
+        self.end_token(Some(b'\n'));
+        let res = String::from_utf8(self.buf.clone()).map_err(|_| std::io::ErrorKind::InvalidData.into());
+        self.count(res)
+    }
+
+    /// Read a field of exactly `n` bytes, including any spaces inside it.
+    /// The field must not span a line break.
+    pub fn read_exact_str(&mut self, n: usize) -> std::io::Result<String> {
+        self.skip_to_field()?;
+        self.buf.clear();
+        while self.buf.len() < n {
+            let buf = self.r.fill_buf_nonempty()?;
+            let l = buf.len().min(n - self.buf.len());
+            if memchr(b'\n', &buf[..l]).is_some() {
+                return Err(std::io::ErrorKind::InvalidData.into());
+            }
+            self.buf.extend_from_slice(&buf[..l]);
+            self.r.consume(l);
+        }
+        String::from_utf8(self.buf.clone()).map_err(|_| std::io::ErrorKind::InvalidData.into())
+    }
+
+    /// Read a field terminated by `delim`, such as a statement ending in
+    /// `;`. Leading whitespace is skipped, everything else up to `delim`
+    /// is returned verbatim, which may span lines. The terminator is
+    /// consumed but not returned, and may be missing at the end of the
+    /// input.
+    pub fn read_until(&mut self, delim: u8) -> std::io::Result<Vec<u8>> {
+        self.skip_to_field()?;
+        let mut res = vec![];
+        let mut any = false;
+        loop {
+            let buf = self.r.fill_buf()?;
+            if buf.is_empty() {
+                if !any {
+                    return Err(std::io::ErrorKind::UnexpectedEof.into());
+                }
+                break;
+            }
+            any = true;
+            if let Some(ix) = memchr(delim, buf) {
+                res.extend_from_slice(&buf[..ix]);
+                self.r.consume(ix+1);
+                break;
+            }
+            res.extend_from_slice(buf);
+            let l = buf.len();
+            self.r.consume(l);
+        }
+        self.end_token(Some(delim));
+        self.count(Ok(res))
     }
 }
 
 #[macro_export]
 macro_rules! input {
-    ($r:ident => $($($v:ident),* : $t:tt),*) => {
+    // declarations whose type is more than one token tree are munched
+    // until the next top level comma
+    (@decl $r:ident [] [$($t:tt)*]) => {};
+    (@decl $r:ident [$v:ident $($vs:ident)*] [$($t:tt)*]) => {
+        let $v = $crate::read_one!($r => $($t)*);
+        $crate::input!(@decl $r [$($vs)*] [$($t)*]);
+    };
+    (@decl $r:ident [$($v:ident)*] [$($t:tt)*] , $($rest:tt)*) => {
+        $crate::input!(@decl $r [$($v)*] [$($t)*]);
+        $crate::input!($r => $($rest)*);
+    };
+    (@decl $r:ident [$($v:ident)*] [$($t:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::input!(@decl $r [$($v)*] [$($t)* $next] $($rest)*);
+    };
+    // `(a, b): T` destructures the tuple read for `T`
+    (@pat $r:ident [$($v:ident)*] [$($t:tt)*]) => {
+        let ($($v),*) = $crate::read_one!($r => $($t)*);
+    };
+    (@pat $r:ident [$($v:ident)*] [$($t:tt)*] , $($rest:tt)*) => {
+        $crate::input!(@pat $r [$($v)*] [$($t)*]);
+        $crate::input!($r => $($rest)*);
+    };
+    (@pat $r:ident [$($v:ident)*] [$($t:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::input!(@pat $r [$($v)*] [$($t)* $next] $($rest)*);
+    };
+    // `_: T` reads a value and drops it
+    (@skip $r:ident [$($t:tt)*]) => {
+        let _ = $crate::read_one!($r => $($t)*);
+    };
+    (@skip $r:ident [$($t:tt)*] , $($rest:tt)*) => {
+        $crate::input!(@skip $r [$($t)*]);
+        $crate::input!($r => $($rest)*);
+    };
+    (@skip $r:ident [$($t:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::input!(@skip $r [$($t)* $next] $($rest)*);
+    };
+    // collects the declared names of a spec into a tuple expression
+    (@names [$($acc:ident)*]) => {
+        ($($acc,)*)
+    };
+    (@names [$($acc:ident)*] eol $(, $($rest:tt)*)?) => {
+        $crate::input!(@names [$($acc)*] $($($rest)*)?)
+    };
+    (@names [$($acc:ident)*] bol $(, $($rest:tt)*)?) => {
+        $crate::input!(@names [$($acc)*] $($($rest)*)?)
+    };
+    (@names [$($acc:ident)*] _ : $($rest:tt)*) => {
+        $crate::input!(@names_skip [$($acc)*] $($rest)*)
+    };
+    (@names [$($acc:ident)*] ($($v:ident),+) : $($rest:tt)*) => {
+        $crate::input!(@names_skip [$($acc)* $($v)*] $($rest)*)
+    };
+    (@names [$($acc:ident)*] $($v:ident),+ : $($rest:tt)*) => {
+        $crate::input!(@names_skip [$($acc)* $($v)*] $($rest)*)
+    };
+    (@names_skip [$acc:ident]) => {
+        $acc
+    };
+    (@names_skip [$($acc:ident)*]) => {
+        ($($acc,)*)
+    };
+    (@names_skip [$($acc:ident)*] , $($rest:tt)*) => {
+        $crate::input!(@names [$($acc)*] $($rest)*)
+    };
+    (@names_skip [$($acc:ident)*] $t:tt $($rest:tt)*) => {
+        $crate::input!(@names_skip [$($acc)*] $($rest)*)
+    };
+    ($r:ident => binary { $($($v:ident),+ : $t:tt),* $(,)? } $(, $($rest:tt)*)?) => {
         $(
             $(
-                let $v = read_one!($r => $t);
-            )*
+                let $v = $crate::read_raw_one!($r => $t);
+            )+
+        )*
+        $crate::input!($r => $($($rest)*)?);
+    };
+    ($r:ident => ) => {};
+    // `eol` and `bol` check the layout of the input instead of reading
+    ($r:ident => eol $(, $($rest:tt)*)?) => {
+        $r.expect_eol().expect("expected end of line");
+        $crate::input!($r => $($($rest)*)?);
+    };
+    ($r:ident => bol $(, $($rest:tt)*)?) => {
+        $r.expect_bol().expect("expected start of line");
+        $crate::input!($r => $($($rest)*)?);
+    };
+    ($r:ident => _ : $($rest:tt)*) => {
+        $crate::input!(@skip $r [] $($rest)*);
+    };
+    // the error for a 0 names the variable
+    ($r:ident => $($v:ident),+ : usize1 $(, $($rest:tt)*)?) => {
+        $(
+            let $v = $r.read_usize1().unwrap_or_else(|e| panic!("failed to read {}: {}", stringify!($v), e));
         )*
+        $crate::input!($r => $($($rest)*)?);
     };
-    ($($($v:ident),* : $t:tt),*) => {
+    ($r:ident => $($v:ident),+ : $t:tt) => {
+        $(
+            let $v = $crate::read_one!($r => $t);
+        )*
+    };
+    ($r:ident => $($v:ident),+ : $t:tt , $($rest:tt)*) => {
+        $(
+            let $v = $crate::read_one!($r => $t);
+        )*
+        $crate::input!($r => $($rest)*);
+    };
+    ($r:ident => $($v:ident),+ : $($rest:tt)*) => {
+        $crate::input!(@decl $r [$($v)*] [] $($rest)*);
+    };
+    ($r:ident => ($($v:ident),+) : $($rest:tt)*) => {
+        $crate::input!(@pat $r [$($v)*] [] $($rest)*);
+    };
+    ($($rest:tt)*) => {
         let input__stdin = ::std::io::stdin();
         let mut input__reader = $crate::FormattedRead::new(input__stdin.lock());
-        input!(input__reader => $($($v),* : $t),*);
+        $crate::input!(input__reader => $($rest)*);
         drop(input__reader);
     };
 }
 
 #[macro_export]
 macro_rules! read_one {
+    // fragments made of several token trees inside of tuples and arrays are
+    // munched until the next top level separator
+    (@tuple $r:ident [$($done:tt)*] []) => {
+        ($($done)*)
+    };
+    (@tuple $r:ident [$($done:tt)*] [$($t:tt)+]) => {
+        ($($done)* $crate::read_one!($r => $($t)+),)
+    };
+    (@tuple $r:ident [$($done:tt)*] [$($t:tt)*] , $($rest:tt)*) => {
+        $crate::read_one!(@tuple $r [$($done)* $crate::read_one!($r => $($t)*),] [] $($rest)*)
+    };
+    (@tuple $r:ident [$($done:tt)*] [$($t:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::read_one!(@tuple $r [$($done)*] [$($t)* $next] $($rest)*)
+    };
+    (@collect $r:ident [$($c:tt)*] [$($t:tt)*] ; $s:expr) => {
+        $crate::repeat_checked($r.max_alloc(), $s, || $crate::read_one!($r => $($t)*)).expect("too many items").collect::<$($c)*>()
+    };
+    (@collect $r:ident [$($c:tt)*] [$($t:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::read_one!(@collect $r [$($c)*] [$($t)* $next] $($rest)*)
+    };
+    (@enumerate $r:ident [$($t:tt)*] ; $s:expr) => {
+        $crate::repeat_checked($r.max_alloc(), $s, || $crate::read_one!($r => $($t)*)).expect("too many items").enumerate().collect::<Vec<_>>()
+    };
+    (@enumerate $r:ident [$($t:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::read_one!(@enumerate $r [$($t)* $next] $($rest)*)
+    };
+    (@iter $r:ident [$($t:tt)*] ; $s:expr) => {
+        (0..$s).map(|_| $crate::read_one!($r => $($t)*))
+    };
+    (@iter $r:ident [$($t:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::read_one!(@iter $r [$($t)* $next] $($rest)*)
+    };
+    (@arr $r:ident [$($t:tt)*] ; const $s:expr) => {
+        [(); $s].map(|_| $crate::read_one!($r => $($t)*))
+    };
+    (@arr $r:ident [$($t:tt)*] ; $s:expr) => {
+        $crate::collect_vec($r.max_alloc(), $s, || $crate::read_one!($r => $($t)*)).expect("failed to read array")
+    };
+    (@arr $r:ident [$($t:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::read_one!(@arr $r [$($t)* $next] $($rest)*)
+    };
+    ($r:ident => enumerate [$($t:tt)*]) => {
+        $crate::read_one!(@enumerate $r [] $($t)*)
+    };
+    ($r:ident => [$($t:tt)*] as $c:ident) => {
+        $crate::read_one!(@collect $r [$c<_>] [] $($t)*)
+    };
+    ($r:ident => [$($t:tt)*] as $c:ty) => {
+        $crate::read_one!(@collect $r [$c] [] $($t)*)
+    };
     ($r:ident => [$t:tt; const $s:tt]) => {
         {
             let mut res = <[$t; $s]>::default();
             for i in 0..$s {
-                res[i] = read_one!($r => $t);
+                res[i] = $crate::read_one!($r => $t);
             }
             res
         }
     };
     ($r:ident => [$t:tt; $s:tt]) => {
-        (0..$s).map(|_| read_one!($r => $t)).collect::<Vec<_>>()
+        $crate::collect_vec($r.max_alloc(), $s, || $crate::read_one!($r => $t)).expect("failed to read array")
+    };
+    ($r:ident => [$($t:tt)*]) => {
+        $crate::read_one!(@arr $r [] $($t)*)
     };
     ($r:ident => ($($t:tt),*)) => {
         ($(
-            read_one!($r => $t),
+            $crate::read_one!($r => $t),
         )*)
     };
+    ($r:ident => ($($t:tt)*)) => {
+        $crate::read_one!(@tuple $r [] [] $($t)*)
+    };
     ($r:ident => usize1) => {
-        read_one!($r => usize) - 1
+        $r.read_usize1().expect("failed to read usize1")
     };
     ($r:ident => {$r2:ident => $($t:tt)*}) => {
         {
@@ -256,14 +1357,525 @@ macro_rules! read_one {
             $($t)*
         }
     };
+    ($r:ident => from($s:expr) {$($spec:tt)*}) => {
+        {
+            let input_src = &$s;
+            let mut input_sub = $crate::FormattedRead::new(::std::convert::AsRef::<[u8]>::as_ref(input_src));
+            $crate::input!(input_sub => $($spec)*);
+            $crate::input!(@names [] $($spec)*)
+        }
+    };
+    ($r:ident => csvline) => {
+        $r.read_csv_record().expect("failed to read csv record")
+    };
+    ($r:ident => time) => {
+        $crate::read_one!($r => $crate::Hms).seconds()
+    };
+    ($r:ident => date($fmt:expr)) => {
+        $r.read_word_with(|w| $crate::Date::from_ascii_fmt(w, $fmt)).expect("failed to read date")
+    };
+    ($r:ident => expr) => {
+        $r.read_expr().expect("failed to read expression").eval().expect("failed to evaluate expression")
+    };
+    ($r:ident => expr_tree) => {
+        $r.read_expr().expect("failed to read expression")
+    };
+    ($r:ident => sqmatrix($n:expr; mod $m:expr)) => {
+        $r.read_sqmatrix_mod($n, $m).expect("failed to read matrix")
+    };
+    ($r:ident => sqmatrix($t:tt; $n:expr)) => {
+        {
+            let input_n = $n;
+            $r.read_matrix::<$t>(input_n, input_n).expect("failed to read matrix")
+        }
+    };
+    ($r:ident => id($table:expr)) => {
+        $r.read_id(&mut $table).expect("failed to read word")
+    };
+    ($r:ident => offsets($k:expr)) => {
+        $r.read_offsets($k, None).expect("failed to read offsets")
+    };
+    ($r:ident => offsets($k:expr; bound = ($n:expr, $m:expr))) => {
+        $r.read_offsets($k, Some(($n, $m))).expect("failed to read offsets")
+    };
+    ($r:ident => seg) => {
+        $r.read_seg(1).expect("failed to read interval")
+    };
+    ($r:ident => seg($first:expr)) => {
+        $r.read_seg($first).expect("failed to read interval")
+    };
+    ($r:ident => poly($t:tt)) => {
+        $r.read_poly::<$t>(false).expect("failed to read polynomial")
+    };
+    ($r:ident => poly($t:tt; rev)) => {
+        $r.read_poly::<$t>(true).expect("failed to read polynomial")
+    };
+    ($r:ident => json) => {
+        $r.read_json().expect("failed to read JSON value")
+    };
+    ($r:ident => bracketed) => {
+        String::from_utf8($r.read_bracketed().expect("failed to read bracket group")).expect("group is not valid UTF-8")
+    };
+    ($r:ident => until($c:expr)) => {
+        {
+            let input_delim = <u8 as ::std::convert::TryFrom<_>>::try_from($c).expect("terminator must be a single byte");
+            String::from_utf8($r.read_until(input_delim).expect("failed to read field")).expect("field is not valid UTF-8")
+        }
+    };
+    ($r:ident => fixed($n:expr)) => {
+        $r.read_exact_str($n).expect("failed to read fixed width field")
+    };
+    ($r:ident => bits($n:expr)) => {
+        $r.read_bits($n).expect("failed to read bits")
+    };
+    ($r:ident => hex) => {
+        $crate::read_one!($r => $crate::Hex).0
+    };
+    ($r:ident => base64) => {
+        $crate::read_one!($r => $crate::Base64).0
+    };
+    ($r:ident => rawchar) => {
+        $r.read_char_raw().expect("failed to read char")
+    };
+    ($r:ident => byte) => {
+        $crate::read_one!($r => $crate::Byte).0
+    };
+    ($r:ident => saturating($t:tt)) => {
+        $r.read_saturating::<$t>().expect("failed to read integer")
+    };
+    ($r:ident => map($k:tt => $v:tt; $n:expr)) => {
+        $crate::repeat_checked($r.max_alloc(), $n, || ($crate::read_one!($r => $k), $crate::read_one!($r => $v))).expect("too many items")
+            .collect::<::std::collections::HashMap<_, _>>()
+    };
+    ($r:ident => either($a:tt | $b:tt)) => {
+        $crate::read_one!($r => $crate::Either<$a, $b>)
+    };
+    ($r:ident => map($t:tt, $f:expr)) => {
+        $crate::apply($crate::read_one!($r => $t), $f)
+    };
+    ($r:ident => fold([$t:tt; $n:expr], $init:expr, $f:expr)) => {
+        $crate::repeat_checked($r.max_alloc(), $n, || $crate::read_one!($r => $t)).expect("too many items").fold($init, $f)
+    };
+    ($r:ident => for_each([$t:tt; $n:expr], $f:expr)) => {
+        $crate::repeat_checked($r.max_alloc(), $n, || $crate::read_one!($r => $t)).expect("too many items").for_each($f)
+    };
+    ($r:ident => btreemap($k:tt => $v:tt; $n:expr)) => {
+        $crate::repeat_checked($r.max_alloc(), $n, || ($crate::read_one!($r => $k), $crate::read_one!($r => $v))).expect("too many items")
+            .collect::<::std::collections::BTreeMap<_, _>>()
+    };
+    ($r:ident => rle($t:tt; $k:expr)) => {
+        $crate::repeat_checked($r.max_alloc(), $k, || ($crate::read_one!($r => $t), $crate::read_one!($r => usize))).expect("too many items")
+            .collect::<Vec<_>>()
+    };
+    ($r:ident => rle($t:tt; $k:expr; $max:expr)) => {
+        $crate::expand_runs(&$crate::read_one!($r => rle($t; $k)), $max).expect("failed to expand runs")
+    };
+    ($r:ident => prefix_sum([$($t:tt)*])) => {
+        $crate::prefix_sums($crate::read_one!(@iter $r [] $($t)*))
+    };
+    ($r:ident => deltas([$($t:tt)*])) => {
+        $crate::deltas($crate::read_one!(@iter $r [] $($t)*))
+    };
+    ($r:ident => sorted_with_idx($t:ty; $n:expr)) => {
+        $r.read_sorted_with_idx::<$t>($n).expect("failed to read values")
+    };
+    ($r:ident => compressed($t:ty; $n:expr)) => {
+        $r.read_compressed::<$t>($n).expect("failed to read values")
+    };
+    ($r:ident => set($t:tt; $n:expr, distinct)) => {
+        {
+            let mut set = ::std::collections::HashSet::new();
+            for x in $crate::repeat_checked($r.max_alloc(), $n, || $crate::read_one!($r => $t)).expect("too many items") {
+                assert!(set.insert(x), "duplicate set element");
+            }
+            set
+        }
+    };
+    ($r:ident => set($t:tt; $n:expr)) => {
+        $crate::repeat_checked($r.max_alloc(), $n, || $crate::read_one!($r => $t)).expect("too many items").collect::<::std::collections::HashSet<_>>()
+    };
+    ($r:ident => btreeset($t:tt; $n:expr, distinct)) => {
+        {
+            let mut set = ::std::collections::BTreeSet::new();
+            for x in $crate::repeat_checked($r.max_alloc(), $n, || $crate::read_one!($r => $t)).expect("too many items") {
+                assert!(set.insert(x), "duplicate set element");
+            }
+            set
+        }
+    };
+    ($r:ident => btreeset($t:tt; $n:expr)) => {
+        $crate::repeat_checked($r.max_alloc(), $n, || $crate::read_one!($r => $t)).expect("too many items").collect::<::std::collections::BTreeSet<_>>()
+    };
+    ($r:ident => array2($t:ty; $n:expr, $m:expr)) => {
+        $r.read_array2::<$t>($n, $m).expect("failed to read matrix")
+    };
+    ($r:ident => svector($t:ty; $d:expr)) => {
+        $r.read_svector::<$t, $d>().expect("failed to read vector")
+    };
+    ($r:ident => vec2($t:ty)) => {
+        $crate::read_one!($r => svector($t; 2))
+    };
+    ($r:ident => vec3($t:ty)) => {
+        $crate::read_one!($r => svector($t; 3))
+    };
+    ($r:ident => smatrix($t:ty; $n:expr, $m:expr)) => {
+        $r.read_smatrix::<$t, $n, $m>().expect("failed to read matrix")
+    };
+    ($r:ident => complex($t:ty)) => {
+        $r.read_complex::<$t>().expect("failed to read complex number")
+    };
+    ($r:ident => grid($n:expr, $m:expr)) => {
+        $r.read_grid($n, $m).expect("failed to read grid")
+    };
+    ($r:ident => grid($n:expr, $m:expr; border = $b:expr)) => {
+        $r.read_grid_bordered($n, $m, $b).expect("failed to read grid")
+    };
+    ($r:ident => func_graph($n:expr)) => {
+        $r.read_func_graph($n).expect("failed to read graph")
+    };
+    ($r:ident => func_graph($n:expr; indeg)) => {
+        {
+            let next = $crate::read_one!($r => func_graph($n));
+            let indeg = $crate::in_degrees(&next);
+            (next, indeg)
+        }
+    };
+    ($r:ident => func_graph($n:expr; cycles)) => {
+        {
+            let next = $crate::read_one!($r => func_graph($n));
+            let on_cycle = $crate::cycle_nodes(&next);
+            (next, on_cycle)
+        }
+    };
+    ($r:ident => graph($n:expr, $m:expr)) => {
+        $r.read_graph($n, $m).expect("failed to read graph")
+    };
+    ($r:ident => digraph($n:expr, $m:expr)) => {
+        $r.read_digraph($n, $m).expect("failed to read graph")
+    };
+    ($r:ident => digraph($n:expr, $m:expr; rev)) => {
+        $r.read_digraph_rev($n, $m).expect("failed to read graph")
+    };
+    ($r:ident => graph($n:expr, $m:expr; ids)) => {
+        $r.read_graph_with_ids($n, $m).expect("failed to read graph")
+    };
+    ($r:ident => digraph($n:expr, $m:expr; ids)) => {
+        $r.read_digraph_with_ids($n, $m).expect("failed to read graph")
+    };
+    ($r:ident => graph($n:expr, $m:expr; $($flag:ident),+)) => {
+        $r.read_graph_filtered($n, $m, $crate::EdgeFilter::new()$(.$flag())+).expect("failed to read graph").0
+    };
+    ($r:ident => digraph($n:expr, $m:expr; $($flag:ident),+)) => {
+        $r.read_digraph_filtered($n, $m, $crate::EdgeFilter::new()$(.$flag())+).expect("failed to read graph").0
+    };
+    ($r:ident => petgraph($n:expr, $m:expr)) => {
+        $r.read_petgraph($n, $m).expect("failed to read graph")
+    };
+    ($r:ident => petgraph($n:expr, $m:expr; $w:ty)) => {
+        $r.read_petgraph_weighted::<$w>($n, $m).expect("failed to read graph")
+    };
+    ($r:ident => petgraph_undirected($n:expr, $m:expr)) => {
+        $r.read_petgraph_undirected($n, $m).expect("failed to read graph")
+    };
+    ($r:ident => petgraph_csr($n:expr, $m:expr)) => {
+        $r.read_petgraph_csr($n, $m).expect("failed to read graph")
+    };
+    ($r:ident => roman) => {
+        $crate::read_one!($r => $crate::Roman).0
+    };
+    ($r:ident => moves($set:ident)) => {
+        $r.read_word_with(|w| $crate::Dir::parse_moves(w, $crate::DirSet::$set)).expect("failed to read moves")
+    };
+    ($r:ident => word(len = $n:expr)) => {
+        {
+            let n = $n;
+            let w = $crate::read_one!($r => String);
+            assert_eq!(w.len(), n, "word {:?} does not have length {}", w, n);
+            w
+        }
+    };
+    ($r:ident => zip($a:tt, $b:tt)) => {
+        {
+            let a = $crate::read_one!($r => $a);
+            let b = $crate::read_one!($r => $b);
+            assert_eq!(a.len(), b.len(), "zipped sequences differ in length");
+            a.into_iter().zip(b).collect::<Vec<_>>()
+        }
+    };
+    ($r:ident => zip($a:tt, $b:tt, $c:tt)) => {
+        {
+            let a = $crate::read_one!($r => $a);
+            let b = $crate::read_one!($r => $b);
+            let c = $crate::read_one!($r => $c);
+            assert!(a.len() == b.len() && b.len() == c.len(), "zipped sequences differ in length");
+            a.into_iter().zip(b).zip(c).map(|((a, b), c)| (a, b, c)).collect::<Vec<_>>()
+        }
+    };
+    ($r:ident => columns($a:tt, $b:tt; $n:expr)) => {
+        $crate::repeat_checked($r.max_alloc(), $n, || {
+            ($crate::read_one!($r => $a), $crate::read_one!($r => $b))
+        })
+        .expect("too many items")
+        .collect::<(Vec<_>, Vec<_>)>()
+    };
+    ($r:ident => columns($a:tt, $b:tt, $c:tt; $n:expr)) => {
+        $crate::repeat_checked($r.max_alloc(), $n, || {
+            ($crate::read_one!($r => $a), $crate::read_one!($r => $b), $crate::read_one!($r => $c))
+        })
+        .expect("too many items")
+        .collect::<(Vec<_>, Vec<_>, Vec<_>)>()
+    };
+    ($r:ident => columns($a:tt, $b:tt, $c:tt, $d:tt; $n:expr)) => {
+        $crate::repeat_checked($r.max_alloc(), $n, || {
+            ($crate::read_one!($r => $a), $crate::read_one!($r => $b), $crate::read_one!($r => $c), $crate::read_one!($r => $d))
+        })
+        .expect("too many items")
+        .collect::<(Vec<_>, Vec<_>, Vec<_>, Vec<_>)>()
+    };
+    ($r:ident => maybe($($t:tt)*)) => {
+        if $r.has_next().expect("failed to read input") {
+            Some($crate::read_one!($r => $($t)*))
+        } else {
+            None
+        }
+    };
+    ($r:ident => rawline) => {
+        $r.read_raw_line().expect("failed to read line")
+    };
+    ($r:ident => lineb) => {
+        $r.read_line_bytes().expect("failed to read line")
+    };
     ($r:ident => line) => {
         $r.read_line::<String>().expect("failed to read line")
     };
     ($r:ident => $t:ty) => {
-        $r.read_word::<$t>().expect(concat!("failed to read ", stringify!($t)))
+        $r.read_value::<$t>().expect(concat!("failed to read ", stringify!($t)))
+    };
+}
+
+
+/// Like `input!`, but read into existing variables instead of declaring new
+/// ones. `Vec`s are cleared and refilled, keeping their allocation, and
+/// arrays read with `const` sizes are overwritten in place.
+///
+/// ```rust
+/// # #[macro_use] extern crate comp_input;
+/// # fn main() {
+/// let mut reader = comp_input::FormattedRead::new(&b"2 1 2 3 3 4 5"[..]);
+/// let mut n = 0;
+/// let mut buf = Vec::with_capacity(100);
+/// for _ in 0..2 {
+///     input_into! { reader => n: usize, buf: [i64; n] }
+/// }
+/// assert_eq!(buf, vec![3, 4, 5]);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! input_into {
+    (@one $r:ident, $v:ident, [$t:tt; const $s:expr]) => {
+        debug_assert_eq!($v.len(), $s);
+        for x in $v.iter_mut() {
+            *x = $crate::read_one!($r => $t);
+        }
+    };
+    (@one $r:ident, $v:ident, [$t:tt; $s:expr]) => {
+        let max_alloc = $r.max_alloc();
+        $crate::refill_vec(&mut $v, max_alloc, $s, || $crate::read_one!($r => $t)).expect("failed to read array");
+    };
+    (@one $r:ident, $v:ident, $t:tt) => {
+        $v = $crate::read_one!($r => $t);
+    };
+    ($r:ident => $($v:ident : $t:tt),* $(,)?) => {
+        $(
+            $crate::input_into!(@one $r, $v, $t);
+        )*
+    };
+}
+
+/// Used by `map(t, f)`, so that the argument type of the closure is known
+/// while checking its body.
+#[doc(hidden)]
+#[inline]
+pub fn apply<T, U, F: FnOnce(T) -> U>(x: T, f: F) -> U {
+    f(x)
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! read_raw_one {
+    ($r:ident => [$t:tt; const $s:expr]) => {
+        [(); $s].map(|_| $crate::read_raw_one!($r => $t))
+    };
+    ($r:ident => [$t:tt; $s:expr]) => {
+        $crate::collect_vec($r.max_alloc(), $s, || $crate::read_raw_one!($r => $t)).expect("failed to read array")
+    };
+    ($r:ident => ($($t:tt),*)) => {
+        ($(
+            $crate::read_raw_one!($r => $t),
+        )*)
+    };
+    ($r:ident => $t:ty) => {
+        $r.read_raw::<$t>().expect(concat!("failed to read raw ", stringify!($t)))
+    };
+}
+
+#[macro_export]
+macro_rules! scan {
+    ($r:ident, $fmt:expr, $($v:ident : $t:ty),* $(,)?) => {
+        let mut scan_state = $r.scan($fmt);
+        $(
+            let $v = scan_state.value::<$t>().expect(concat!("failed to scan ", stringify!($v)));
+        )*
+        scan_state.finish().expect(concat!("input does not match ", stringify!($fmt)));
+    };
+}
+
+/// Define a fieldless enum parsed from fixed keywords, e.g.
+///
+/// ```rust
+/// # #[macro_use] extern crate comp_input;
+/// str_enum! {
+///     pub enum Cmd { Add = "ADD", Remove = "REMOVE" }
+/// }
+/// str_enum! {
+///     enum Side (ignore_case) { Left = "left", Right = "right" }
+/// }
+/// # fn main() {}
+/// ```
+///
+/// Unknown words fail to parse. With `(ignore_case)`, keywords are matched
+/// ignoring ASCII case.
+#[macro_export]
+macro_rules! str_enum {
+    (@impl $name:ident $cmp:ident { $($variant:ident = $s:literal),* }) => {
+        impl $name {
+            /// The keyword of this variant.
+            #[allow(dead_code)]
+            pub fn as_str(self) -> &'static str {
+                match self {
+                    $($name::$variant => $s,)*
+                }
+            }
+        }
+
+        impl $crate::FromAscii for $name {
+            fn from_ascii(src: &[u8]) -> Option<$name> {
+                $(
+                    if src.$cmp($s.as_bytes()) {
+                        return Some($name::$variant);
+                    }
+                )*
+                None
+            }
+        }
+    };
+    ($(#[$m:meta])* $vis:vis enum $name:ident (ignore_case) { $($variant:ident = $s:literal),* $(,)? }) => {
+        $(#[$m])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        $vis enum $name { $($variant),* }
+        $crate::str_enum!(@impl $name eq_ignore_ascii_case { $($variant = $s),* });
+    };
+    ($(#[$m:meta])* $vis:vis enum $name:ident { $($variant:ident = $s:literal),* $(,)? }) => {
+        $(#[$m])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        $vis enum $name { $($variant),* }
+        $crate::str_enum!(@impl $name eq { $($variant = $s),* });
     };
 }
 
+mod answers;
+pub mod bench;
+mod binary;
+mod bitset;
+mod brackets;
+#[cfg(any(not(feature = "memchr"), feature = "bundle"))]
+mod byte_search;
+mod capture;
+mod cases;
+mod collections;
+mod combinators;
+mod command;
+mod comments;
+mod config;
+mod counted;
+mod csv;
+mod either;
+mod encoding;
+#[cfg(feature = "expr")]
+mod expr;
+mod ext;
+#[cfg(feature = "fetch")]
+mod fetch;
+mod generate;
+mod geom;
+mod graph;
+mod grid;
+mod input_writer;
+#[cfg(feature = "json")]
+mod json;
+mod judge;
+mod matrix;
+pub mod prelude;
+mod queue;
+mod raw_capture;
+mod poly;
+mod rng;
+mod roman;
+mod run;
+mod samples;
+mod scan;
+mod seg;
+mod seq;
+mod shrink;
+mod small_word;
+mod stress;
+mod string_table;
+mod time;
+mod trace;
+mod unicode;
+mod word_hash;
+mod writer;
+
+pub use answers::Answers;
+pub use binary::FromLeBytes;
+pub use bitset::BitSet;
+pub use cases::CaseReader;
+pub use collections::{expand_runs, Compressed, Duplicates};
+pub use command::CommandSource;
+pub use comments::SkipComments;
+pub use config::{is_local, ECHO_ENV_VAR, LOCAL_ENV_VAR};
+pub use either::Either;
+pub use encoding::{Base64, Hex};
+#[cfg(feature = "expr")]
+pub use expr::{Expr, Op};
+#[cfg(feature = "fetch")]
+pub use fetch::{fetch_samples, parse_samples};
+#[cfg(feature = "tokio")]
+pub use ext::tokio::AsyncFormattedRead;
+pub use geom::{Dir, DirSet, Point2, Point3};
+pub use graph::{cycle_nodes, in_degrees, Adj, AdjIds, Dropped, EdgeFilter};
+pub use grid::{DIRS4, DIRS8};
+pub use input_writer::{assert_round_trip, InputWriter, ToInput};
+pub use judge::{judge_command, Limits, Outcome, Verdict};
+pub use matrix::Matrix;
+pub use poly::Poly;
+pub use queue::ByteQueue;
+pub use raw_capture::RawCapture;
+pub use rng::Rng;
+pub use roman::Roman;
+pub use run::run_with_stack;
+pub use samples::{run_samples, run_samples_command, run_samples_timed, tokens_match};
+pub use scan::Scan;
+pub use seg::Seg;
+pub use seq::{deltas, prefix_sums};
+pub use shrink::{shrink_failing, Shrink, WithLen};
+pub use small_word::SmallWord;
+pub use stress::{all_arrays, all_trees, check_all, check_shrinking};
+pub use string_table::StringTable;
+pub use time::{Date, Hms};
+pub use trace::install_panic_hook;
+pub use word_hash::{hash_base, hash_pow, WordHash, HASH_MOD};
+pub use writer::{install_panic_flush, FormattedWrite, PutInt};
 
 #[test]
 fn test_graph() {
@@ -302,3 +1914,698 @@ fn test_crlf() {
     assert_eq!(e, "Fino.");
 
 }
+
+#[test]
+fn test_from() {
+    let input = b"2\n3 -1 5 7\n4 2\n";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+
+    input! {
+        reader =>
+            t: usize,
+            first: line,
+            x: from(first) { a: usize, b: [i64; a] },
+            y: from(read_one!(reader => line)) { c, d: u32 },
+    }
+
+    assert_eq!(t, 2);
+    assert_eq!(x, (3, vec![-1, 5, 7]));
+    assert_eq!(y, (4, 2));
+}
+
+#[test]
+fn test_delimiters() {
+    let input = b"3,4,5\n1, 2;3\n";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]))
+        .with_delimiters(b", ;\n");
+
+    input! {
+        reader =>
+            a: [u32; 3],
+            b: [u8; const 3],
+    }
+
+    assert_eq!(a, vec![3, 4, 5]);
+    assert_eq!(b, [1, 2, 3]);
+}
+
+#[test]
+fn test_fixed() {
+    let input = b"2\n  1ab c\n 12 x y\n";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]))
+        .with_leading_whitespace(true);
+
+    input! {
+        reader =>
+            n: usize,
+            rows: [(fixed(3), fixed(4)); n],
+    }
+
+    assert_eq!(rows, vec![
+        ("  1".to_owned(), "ab c".to_owned()),
+        (" 12".to_owned(), " x y".to_owned()),
+    ]);
+
+    let input = b"  ab\ncd";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+    assert_eq!(reader.read_exact_str(2).unwrap(), "ab");
+    assert!(reader.read_exact_str(3).is_err());
+}
+
+#[test]
+fn test_rawchar() {
+    let input = b"7\n ab c\t\n";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+
+    input! {
+        reader =>
+            n: u32,
+            a: [rawchar; 3],
+            b: char,
+    }
+
+    assert_eq!(n, 7);
+    assert_eq!(a, vec![' ', 'a', 'b']);
+    assert_eq!(b, 'c');
+
+    assert_eq!(reader.read_char_raw().unwrap(), '\n');
+    assert!(reader.read_char_raw().is_err());
+}
+
+#[test]
+fn test_byte() {
+    let input = b"# . #\na b\n";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+
+    input! {
+        reader =>
+            row: [byte; 3],
+            pair: (Byte, byte),
+    }
+
+    assert_eq!(row, vec![b'#', b'.', b'#']);
+    assert_eq!(pair, (Byte(b'a'), b'b'));
+    assert!(reader.read_word::<Byte>().is_err());
+}
+
+#[test]
+fn test_wrappers() {
+    use std::num::{Saturating, Wrapping};
+
+    let input = b"18446744073709551615 2 250 10\n";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+
+    input! {
+        reader =>
+            a, b: Wrapping<u64>,
+            c, d: Saturating<u8>,
+    }
+
+    assert_eq!(a + b, Wrapping(1));
+    assert_eq!(c + d, Saturating(255));
+}
+
+#[test]
+fn test_map_token() {
+    let input = b"4000000000 1 2 5 x";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+    input! {
+        reader =>
+            a: map(u32, |x| x as u64 * 2),
+            b: [map(usize1, |i| i.max(1)); 2],
+            c: map((u8, String), |(n, s)| s.repeat(n as usize)),
+    }
+    assert_eq!(a, 8_000_000_000);
+    assert_eq!(b, vec![1, 1]);
+    assert_eq!(c, "xxxxx");
+}
+
+#[test]
+fn test_fold() {
+    let input = b"4 3 -1 7 2 3 1 5 9";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+    input! {
+        reader =>
+            n: usize,
+            sum: fold([i64; n], 0, |acc, x| acc + x),
+            m: usize,
+            max: fold([u32; m], 0, u32::max),
+    }
+    assert_eq!(sum, 11);
+    assert_eq!(max, 9);
+}
+
+#[test]
+fn test_for_each() {
+    let input = b"3 2 1 2 2 3 x 10";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+    input! { reader => n, m: usize }
+    let mut adj = vec![vec![]; n];
+    input! {
+        reader =>
+            _: for_each([(usize1, usize1); m], |(u, v)| adj[u].push(v)),
+            _: String,
+    }
+    assert_eq!(adj, vec![vec![1], vec![2], vec![]]);
+    let x = read_one!(reader => from("7 8") { _: u8, x: u8 });
+    assert_eq!((x, reader.read_word::<u8>().unwrap()), (8, 10));
+}
+
+#[test]
+fn test_max_alloc() {
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&b"3 1 2 3 1000 1 2 3"[..])).with_max_alloc(64);
+    input! { reader => n: usize, a: [i64; n], m: u64 }
+    assert_eq!(a, vec![1, 2, 3]);
+    let err = crate::collect_vec(reader.max_alloc(), m, || reader.read_word::<i64>().unwrap()).unwrap_err();
+    assert_eq!(err.to_string(), "1000 items of 8 bytes exceed the allocation limit of 64 bytes");
+    let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        input! { reader => _b: [i64; m] }
+    }));
+    assert!(res.is_err());
+    let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let mut b = vec![];
+        input_into! { reader => b: [i64; m] }
+        b
+    }));
+    assert!(res.unwrap_err().downcast_ref::<String>().unwrap().contains("allocation limit"));
+    let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        input! { reader => binary { _c: [u64; m] } }
+    }));
+    assert!(res.unwrap_err().downcast_ref::<String>().unwrap().contains("allocation limit"));
+    let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        input! { reader => _d: [i64; m] as std::collections::HashSet<_> }
+    }));
+    assert!(res.unwrap_err().downcast_ref::<String>().unwrap().contains("allocation limit"));
+    let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        input! { reader => _e: set(i64; m) }
+    }));
+    assert!(res.unwrap_err().downcast_ref::<String>().unwrap().contains("allocation limit"));
+    let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        input! { reader => _f: columns(u32, u32; m) }
+    }));
+    assert!(res.unwrap_err().downcast_ref::<String>().unwrap().contains("allocation limit"));
+    assert!(reader.read_set::<i64>(1000, crate::Duplicates::Ignore).is_err());
+}
+
+#[test]
+fn test_saturating() {
+    let input = b"99999999999999999999999 -99999999999999999999999 -3 300 42 -";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+    input! {
+        reader =>
+            a, b: saturating(i64),
+            c: saturating(u32),
+            d, e: saturating(u8),
+    }
+    assert_eq!((a, b), (i64::MAX, i64::MIN));
+    assert_eq!((c, d, e), (0, 255, 42));
+    assert!(reader.read_saturating::<i32>().is_err());
+}
+
+#[test]
+fn test_str_enum() {
+    str_enum! {
+        enum Cmd { Add = "ADD", Remove = "REMOVE" }
+    }
+    str_enum! {
+        enum Side (ignore_case) { Left = "LEFT", Right = "RIGHT" }
+    }
+
+    let input = b"ADD REMOVE left Right add\n";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+
+    input! {
+        reader =>
+            a, b: Cmd,
+            c: [Side; 2],
+    }
+
+    assert_eq!((a, b), (Cmd::Add, Cmd::Remove));
+    assert_eq!(c, vec![Side::Left, Side::Right]);
+    assert_eq!(c[0].as_str(), "LEFT");
+    assert!(reader.read_word::<Cmd>().is_err());
+}
+
+#[test]
+fn test_word_len() {
+    let input = b"3 abc\n2 abc\n";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+
+    input! {
+        reader =>
+            n: usize,
+            s: word(len = n),
+    }
+    assert_eq!(s, "abc");
+
+    let res = std::panic::catch_unwind(move || {
+        input! {
+            reader =>
+                n: usize,
+                s: word(len = n),
+        }
+        s
+    });
+    assert!(res.unwrap_err().downcast_ref::<String>().unwrap().contains("does not have length 2"));
+}
+
+#[test]
+fn test_input_into() {
+    let input = b"2\n3 1 2 3 a b\n1 4 c d\n";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+
+    input! {
+        reader =>
+            t: usize,
+    }
+
+    let mut n = 0;
+    let mut a = Vec::with_capacity(16);
+    let mut b = ['-'; 2];
+    let ptr = a.as_ptr();
+    for _ in 0..t {
+        input_into! {
+            reader =>
+                n: usize,
+                a: [u32; n],
+                b: [char; const 2],
+        }
+    }
+
+    assert_eq!(n, 1);
+    assert_eq!(a, vec![4]);
+    assert_eq!(a.as_ptr(), ptr);
+    assert_eq!(b, ['c', 'd']);
+}
+
+#[test]
+fn test_enumerate() {
+    let input = b"3\n5 1\n2 2\n7 1\n";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+
+    input! {
+        reader =>
+            n: usize,
+            queries: enumerate [(u32, usize1); n],
+    }
+
+    assert_eq!(queries, vec![(0, (5, 0)), (1, (2, 1)), (2, (7, 0))]);
+}
+
+#[test]
+fn test_zip() {
+    let input = b"3\n1 2 3\n-1 -2 -3\n2\na b\n5 6\nx y\n";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+
+    input! {
+        reader =>
+            n: usize,
+            ab: zip([u32; n], [i64; n]),
+            m: usize,
+            cde: zip([char; m], [u8; m], [String; m]),
+    }
+
+    assert_eq!(ab, vec![(1, -1), (2, -2), (3, -3)]);
+    assert_eq!(cde, vec![('a', 5, "x".to_owned()), ('b', 6, "y".to_owned())]);
+}
+
+#[test]
+fn test_columns() {
+    let input = b"2\nalice 90 31\nbob 75 27\n1 2\n3 4\n5 6\n";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+
+    input! {
+        reader =>
+            n: usize,
+            (names, scores, ages): columns(String, i64, u32; n),
+            xy: columns(usize1, u8; 1),
+            (p, q): (u8, u8),
+    }
+
+    assert_eq!(names, vec!["alice", "bob"]);
+    assert_eq!(scores, vec![90, 75]);
+    assert_eq!(ages, vec![31, 27]);
+    assert_eq!(xy, (vec![0], vec![2]));
+    assert_eq!((p, q), (3, 4));
+
+    input! {
+        reader =>
+            r: from("7 8") { (a, b): (u8, u8) },
+    }
+    assert_eq!(r, (7, 8));
+}
+
+#[test]
+fn test_raw_line() {
+    let input = b"2\r\n  indented \r\n\nlast";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+
+    input! {
+        reader =>
+            n: usize,
+            lines: [rawline; n + 1],
+    }
+
+    assert_eq!(lines, vec!["  indented ", "", "last"]);
+    assert!(reader.read_raw_line().is_err());
+}
+
+#[test]
+fn test_line_chunks() {
+    let input = b"3\r\nab c\r\n\r\nx\ry\r\nlast\r";
+    for &capacity in &[1, 2, 3, 64] {
+        let mut reader = FormattedRead::new(std::io::BufReader::with_capacity(capacity, &input[..]));
+
+        input! {
+            reader =>
+                n: usize,
+                a: rawline,
+                b: rawline,
+                c: line,
+                d: [rawline; n - 2],
+        }
+
+        assert_eq!(n, 3);
+        assert_eq!(a, "ab c");
+        assert_eq!(b, "");
+        assert_eq!(c, "x\ry");
+        assert_eq!(d, vec!["last"]);
+        assert!(reader.read_raw_line().is_err());
+
+        let mut reader = FormattedRead::new(std::io::BufReader::with_capacity(capacity, &input[..]))
+            .with_cr_newlines(true);
+        reader.read_raw_line().unwrap();
+        reader.read_raw_line().unwrap();
+        assert_eq!(reader.read_line::<String>().unwrap(), "x");
+        assert_eq!(reader.read_line::<String>().unwrap(), "y");
+        assert_eq!(reader.read_raw_line().unwrap(), "last");
+    }
+}
+
+#[test]
+fn test_read_until_eof() {
+    let input = b"1 2\n3\n\n4";
+    for &capacity in &[1, 64] {
+        let mut reader = FormattedRead::new(std::io::BufReader::with_capacity(capacity, &input[..]));
+        let mut v = vec![];
+        while let Some(x) = reader.read_word_opt::<u32>().unwrap() {
+            v.push(x);
+        }
+        assert_eq!(v, vec![1, 2, 3, 4]);
+        assert_eq!(reader.read_word_opt::<u32>().unwrap(), None);
+    }
+
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&b"1 x \n "[..]));
+    assert_eq!(reader.read_word_opt::<u32>().unwrap(), Some(1));
+    assert!(reader.read_word_opt::<u32>().is_err());
+}
+
+#[test]
+fn test_position() {
+    let input = b"12 abc\r\nline two\n7 x";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+
+    input! {
+        reader =>
+            a: u32,
+            b: String,
+            c: line,
+    }
+
+    assert_eq!((a, b.as_str(), c.as_str()), (12, "abc", "line two"));
+    assert_eq!(reader.tokens_read(), 3);
+    assert_eq!(reader.bytes_consumed(), 17);
+    assert!(reader.read_word::<u32>().is_ok());
+    assert!(reader.read_word::<u32>().is_err());
+    assert_eq!(reader.tokens_read(), 4);
+}
+
+#[test]
+fn test_failed_parse_keeps_word() {
+    let input = b"12345 abcdef -7 x";
+    // a tiny buffer, so that words span several chunks
+    let mut reader = FormattedRead::new(std::io::BufReader::with_capacity(2, &input[..]));
+    assert_eq!(reader.read_word::<u32>().unwrap(), 12345);
+    assert!(reader.read_word::<u32>().is_err());
+    assert_eq!(reader.bytes_consumed(), 6);
+    assert_eq!(reader.read_word::<String>().unwrap(), "abcdef");
+    assert!(reader.read_word::<u8>().is_err());
+    assert_eq!(reader.read_word::<i8>().unwrap(), -7);
+    assert!(reader.read_word::<f64>().is_err());
+    assert_eq!(reader.read_word::<char>().unwrap(), 'x');
+    assert_eq!(reader.tokens_read(), 4);
+
+    let input = "ж1 2ж x".as_bytes();
+    let mut reader = FormattedRead::new(std::io::Cursor::new(input)).with_unicode(true);
+    assert!(reader.read_word::<u32>().is_err());
+    assert_eq!(reader.read_word::<String>().unwrap(), "ж1");
+    assert!(reader.read_word::<u32>().is_err());
+    input! { reader => a, b: String }
+    assert_eq!((a, b), ("2ж".to_string(), "x".to_string()));
+}
+
+#[test]
+fn test_read_until() {
+    let input = b"x = 1 + 2;\n  print x;y";
+    let mut reader = FormattedRead::new(std::io::BufReader::with_capacity(3, &input[..]));
+    input! { reader => a: until(';'), b: until(b';') }
+    assert_eq!(a, "x = 1 + 2");
+    assert_eq!(b, "print x");
+    assert_eq!(reader.read_until(b';').unwrap(), b"y");
+    assert!(reader.read_until(b';').is_err());
+}
+
+#[test]
+fn test_max_token_len() {
+    let input = b"123 45678 9 1234567890123";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+    assert_eq!(reader.read_word_max_len::<u32>(3).unwrap(), 123);
+    assert!(reader.read_word_max_len::<u32>(3).is_err());
+
+    let mut reader = FormattedRead::new(std::io::BufReader::with_capacity(4, &input[..]))
+        .with_max_token_len(5);
+    input! {
+        reader =>
+            a, b, c: u64,
+    }
+    assert_eq!((a, b, c), (123, 45678, 9));
+    let err = reader.read_word::<u64>().unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    assert_eq!(reader.tokens_read(), 3);
+}
+
+#[test]
+fn test_eol_bol() {
+    let input = b"3\n1 2 3\n";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+    input! {
+        reader =>
+            bol,
+            n: usize,
+            eol, bol,
+            a: [i64; n],
+            eol,
+    }
+    assert_eq!(a, vec![1, 2, 3]);
+    assert!(reader.expect_eol().is_ok());
+
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&b"1 2\n\n3"[..]));
+    assert_eq!(reader.read_word::<u32>().unwrap(), 1);
+    assert!(reader.expect_eol().is_err());
+    assert!(reader.expect_bol().is_err());
+    assert_eq!(reader.read_word::<u32>().unwrap(), 2);
+    assert!(reader.expect_eol().is_ok());
+    assert!(reader.expect_eol().is_ok());
+    assert!(reader.expect_eol().is_err());
+    assert!(reader.expect_bol().is_ok());
+}
+
+#[test]
+fn test_whitespace_policy() {
+    let input = b"1 2\n3  4\n";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]))
+        .with_whitespace(Whitespace::SingleSpace);
+    assert_eq!(reader.read_word::<u32>().unwrap(), 1);
+    assert_eq!(reader.read_word::<u32>().unwrap(), 2);
+    assert_eq!(reader.read_word::<u32>().unwrap(), 3);
+    assert!(reader.read_word::<u32>().is_err());
+
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&b" a b\nx\t\r\n"[..]))
+        .with_whitespace(Whitespace::SingleSpace);
+    assert!(reader.read_line::<String>().is_err());
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&b"a b\nx\t\r\n"[..]))
+        .with_whitespace(Whitespace::SingleSpace);
+    assert_eq!(reader.read_line::<String>().unwrap(), "a b");
+    assert!(reader.read_word::<String>().is_err());
+
+    // a space at the end of a line or of the input is rejected
+    for mode in [Whitespace::SingleSpace, Whitespace::Exact] {
+        let mut reader = FormattedRead::new(&b"3 \n"[..]).with_whitespace(mode);
+        assert!(reader.read_word::<u32>().is_err());
+        let mut reader = FormattedRead::new(&b"3 "[..]).with_whitespace(mode);
+        assert!(reader.read_word::<u32>().is_err());
+        let mut reader = FormattedRead::new(&b"3 4\n"[..]).with_whitespace(mode);
+        input! { reader => n: usize, m: usize, eol }
+        assert_eq!((n, m), (3, 4));
+    }
+    let mut reader = FormattedRead::new(&b"3 \n"[..]);
+    input! { reader => n: usize, eol }
+    assert_eq!(n, 3);
+
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&b"a b\nx\t\r\n"[..]))
+        .with_whitespace(Whitespace::Exact);
+    assert_eq!(reader.read_line::<String>().unwrap(), "a b");
+    assert!(reader.read_word::<String>().is_err());
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&b"a\r\n"[..]))
+        .with_whitespace(Whitespace::Exact);
+    assert!(reader.read_line::<String>().is_err());
+}
+
+#[test]
+fn test_maybe() {
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&b"2 1 2\n3\n"[..]));
+    input! {
+        reader =>
+            n: usize,
+            a: [u32; n],
+            b: maybe(u32),
+            c: maybe((u32, String)),
+    }
+    assert_eq!(a, vec![1, 2]);
+    assert_eq!(b, Some(3));
+    assert_eq!(c, None);
+    assert!(!reader.has_next().unwrap());
+}
+
+#[test]
+fn test_line_bytes() {
+    let input = b"caf\xe9 au lait\r\n  ok\n";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+    input! {
+        reader =>
+            a: lineb,
+            b: lineb,
+    }
+    assert_eq!(a, b"caf\xe9 au lait");
+    assert_eq!(b, b"ok");
+
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+    assert!(reader.read_line::<String>().is_err());
+}
+
+#[test]
+fn test_bom() {
+    let input = b"\xef\xbb\xbf12 34";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+    input! { reader => a, b: u32 }
+    assert_eq!((a, b), (12, 34));
+    assert_eq!(reader.bytes_consumed(), 8);
+
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..])).with_bom_skip(false);
+    assert!(reader.read_word::<u32>().is_err());
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]))
+        .with_whitespace(Whitespace::Exact);
+    assert_eq!(reader.read_word::<u32>().unwrap(), 12);
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..])).strict();
+    assert!(reader.read_word::<u32>().is_err());
+}
+
+#[test]
+fn test_decimal_comma() {
+    let input = b"3,25 -0,5 7 2.5 a,b";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..])).with_decimal_comma(true);
+    input! {
+        reader =>
+            a: [f64; 4],
+            s: String,
+    }
+    assert_eq!(a, vec![3.25, -0.5, 7.0, 2.5]);
+    assert_eq!(s, "a,b");
+
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+    assert!(reader.read_word::<f64>().is_err());
+}
+
+#[test]
+fn test_float_policy() {
+    let input = b"1e9 inf NaN 2.5";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+    input! { reader => a: [f64; 4] }
+    assert_eq!(a[0], 1e9);
+    assert!(a[1].is_infinite() && a[2].is_nan());
+
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]))
+        .with_float_exponent(false)
+        .with_float_inf_nan(false);
+    assert!(reader.read_word::<f64>().is_err());
+    reader.read_word::<String>().unwrap();
+    assert!(reader.read_word::<f64>().is_err());
+    reader.read_word::<String>().unwrap();
+    assert!(reader.read_word::<f32>().is_err());
+    reader.read_word::<String>().unwrap();
+    assert_eq!(reader.read_word::<f64>().unwrap(), 2.5);
+
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&b"1e3"[..]))
+        .with_whitespace(Whitespace::Exact);
+    assert_eq!(reader.read_word::<f64>().unwrap(), 1e3);
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&b"1e3"[..])).strict();
+    assert!(reader.read_word::<f64>().is_err());
+}
+
+#[test]
+fn test_digit_separator() {
+    let input = b"1_000_000 -12_345 7 1__0 _1";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..])).with_digit_separator(b'_');
+    input! { reader => a: u64, b: i32, c: usize }
+    assert_eq!((a, b, c), (1_000_000, -12_345, 7));
+    assert!(reader.read_word::<u32>().is_err());
+    reader.read_word::<String>().unwrap();
+    assert!(reader.read_word::<u32>().is_err());
+
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&b"1,000"[..])).with_digit_separator(b',');
+    assert_eq!(reader.read_word::<u16>().unwrap(), 1000);
+}
+
+#[test]
+fn test_negative_unsigned() {
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&b"-5 x"[..]));
+    let err = reader.read_word::<usize>().unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    let inner = err.get_ref().and_then(|e| e.downcast_ref::<ParseError>());
+    assert_eq!(inner, Some(&ParseError::NegativeUnsigned("-5".to_string())));
+    assert_eq!(reader.read_word::<i32>().unwrap(), -5);
+    assert!(reader.read_word::<u8>().unwrap_err().get_ref().is_none());
+
+    let input = b"-5 7 -1_000";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]))
+        .with_negative_unsigned(NegativeUnsigned::Abs)
+        .with_digit_separator(b'_');
+    input! { reader => a: [u32; 3] }
+    assert_eq!(a, vec![5, 7, 1000]);
+
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&b"-5 -"[..]))
+        .with_negative_unsigned(NegativeUnsigned::Saturate);
+    assert_eq!(reader.read_word::<u64>().unwrap(), 0);
+    assert!(reader.read_word::<u64>().is_err());
+}
+
+#[test]
+fn test_usize1() {
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&b"3 1 0 x"[..]));
+    input! { reader => a, b: usize1 }
+    assert_eq!((a, b), (2, 0));
+    let err = reader.read_usize1().unwrap_err();
+    let inner = err.get_ref().and_then(|e| e.downcast_ref::<ParseError>());
+    assert_eq!(inner, Some(&ParseError::ZeroIndex("0".to_string())));
+    reader.read_word::<String>().unwrap();
+    assert!(reader.read_usize1().unwrap_err().get_ref().is_none());
+
+    let res = std::panic::catch_unwind(|| {
+        let mut reader = FormattedRead::new(&b"0"[..]);
+        input! { reader => idx: usize1 }
+        idx
+    });
+    let msg = res.unwrap_err();
+    let msg = msg.downcast_ref::<String>().unwrap();
+    assert_eq!(msg, "failed to read idx: zero for 1-based index: 0");
+}
@@ -0,0 +1,91 @@
+//! Dense matrices stored row by row in a single `Vec`.
+
+use std::io::{self, BufRead};
+use std::ops::{Index, IndexMut};
+
+use crate::{FormattedRead, FromTokens};
+
+/// A `rows` by `cols` matrix, stored row by row in one allocation and
+/// indexed by `(i, j)`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Matrix<T> {
+    rows: usize,
+    cols: usize,
+    data: Vec<T>,
+}
+
+impl<T> Matrix<T> {
+    /// The matrix with the given entries, row by row. Panics unless there
+    /// are `rows * cols` of them.
+    pub fn from_vec(rows: usize, cols: usize, data: Vec<T>) -> Self {
+        assert_eq!(data.len(), rows * cols, "wrong number of matrix entries");
+        Matrix { rows, cols, data }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn row(&self, i: usize) -> &[T] {
+        &self.data[i * self.cols..(i + 1) * self.cols]
+    }
+
+    /// All entries, row by row.
+    pub fn data(&self) -> &[T] {
+        &self.data
+    }
+}
+
+impl<T> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        assert!(j < self.cols);
+        &self.data[i * self.cols + j]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        assert!(j < self.cols);
+        &mut self.data[i * self.cols + j]
+    }
+}
+
+impl<R: BufRead> FormattedRead<R> {
+    /// Read `n * m` entries of an `n` by `m` matrix, row by row.
+    pub fn read_matrix<T: FromTokens>(&mut self, n: usize, m: usize) -> io::Result<Matrix<T>> {
+        let len = n.checked_mul(m).filter(|l| l.saturating_mul(std::mem::size_of::<T>()) <= self.max_alloc());
+        let len = len.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "matrix exceeds the allocation limit"))?;
+        let mut data = Vec::with_capacity(len);
+        for _ in 0..len {
+            data.push(self.read_value()?);
+        }
+        Ok(Matrix::from_vec(n, m, data))
+    }
+
+    /// Read an `n` by `n` matrix of integers, reducing each entry modulo
+    /// `modulus` into `0..modulus`, e.g. for matrix powers modulo a prime.
+    pub fn read_sqmatrix_mod(&mut self, n: usize, modulus: i64) -> io::Result<Matrix<i64>> {
+        let mut a = self.read_matrix::<i64>(n, n)?;
+        for x in &mut a.data {
+            *x = x.rem_euclid(modulus);
+        }
+        Ok(a)
+    }
+}
+
+#[test]
+fn test_sqmatrix() {
+    let input = b"2 1 2 3 4 -1 7 1000000008 5";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+    input! { reader => n: usize, a: sqmatrix(u32; n), b: sqmatrix(n; mod 1_000_000_007) }
+    assert_eq!(a.row(1), &[3, 4]);
+    assert_eq!(a[(0, 1)], 2);
+    assert_eq!(b.data(), &[1_000_000_006, 7, 1, 5]);
+    assert!(reader.read_matrix::<u64>(1 << 40, 1 << 40).is_err());
+}
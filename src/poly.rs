@@ -0,0 +1,83 @@
+//! Polynomials given by their degree and coefficients.
+
+use std::io::{self, BufRead};
+use std::ops::{Add, Mul};
+
+use crate::{FormattedRead, FromTokens};
+
+/// A polynomial with coefficients of type `T`, lowest degree first.
+/// Trailing zero coefficients are removed, so that equal polynomials have
+/// equal representations and the zero polynomial has no coefficients.
+///
+/// Read as the degree `d` followed by the `d + 1` coefficients
+/// `a_0 a_1 ... a_d`. Use `poly(T; rev)` for inputs that start with the
+/// leading coefficient.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Poly<T> {
+    coeffs: Vec<T>,
+}
+
+impl<T: Default + PartialEq> Poly<T> {
+    /// The polynomial with coefficients `coeffs`, lowest degree first.
+    pub fn new(mut coeffs: Vec<T>) -> Self {
+        let zero = T::default();
+        while coeffs.last() == Some(&zero) {
+            coeffs.pop();
+        }
+        Poly { coeffs }
+    }
+}
+
+impl<T> Poly<T> {
+    /// The degree, `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.coeffs.len().checked_sub(1)
+    }
+
+    pub fn coeffs(&self) -> &[T] {
+        &self.coeffs
+    }
+
+    pub fn into_coeffs(self) -> Vec<T> {
+        self.coeffs
+    }
+}
+
+impl<T: Copy + Default + Add<Output = T> + Mul<Output = T>> Poly<T> {
+    /// The value at `x`, by Horner's scheme.
+    pub fn eval(&self, x: T) -> T {
+        self.coeffs.iter().rev().fold(T::default(), |acc, &a| acc * x + a)
+    }
+}
+
+impl<R: BufRead> FormattedRead<R> {
+    /// Read a degree followed by the coefficients of a `Poly`, lowest
+    /// degree first, or highest first if `rev` is set.
+    pub fn read_poly<T: FromTokens + Default + PartialEq>(&mut self, rev: bool) -> io::Result<Poly<T>> {
+        let d: usize = self.read_word()?;
+        let mut coeffs = (0..=d).map(|_| self.read_value()).collect::<io::Result<Vec<T>>>()?;
+        if rev {
+            coeffs.reverse();
+        }
+        Ok(Poly::new(coeffs))
+    }
+}
+
+impl<T: FromTokens + Default + PartialEq> FromTokens for Poly<T> {
+    fn from_tokens<R: BufRead>(r: &mut FormattedRead<R>) -> io::Result<Self> {
+        r.read_poly(false)
+    }
+}
+
+#[test]
+fn test_poly() {
+    let input = b"2 1 0 3 3 1 -2 0 0 1 4 0";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+    input! { reader => p: poly(i64), q: Poly<i32>, r: poly(u8; rev) }
+    assert_eq!(p.coeffs(), &[1, 0, 3]);
+    assert_eq!(p.eval(2), 13);
+    assert_eq!(q.degree(), Some(1));
+    assert_eq!(q, Poly::new(vec![1, -2]));
+    assert_eq!(r.coeffs(), &[0, 4]);
+    assert_eq!(Poly::new(vec![0u8]).degree(), None);
+}
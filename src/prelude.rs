@@ -0,0 +1,21 @@
+//! The macros, reader types, tokens and traits of the crate, for use with
+//! a single glob import:
+//!
+//! ```rust
+//! use comp_input::prelude::*;
+//!
+//! let mut reader = FormattedRead::new(&b"3 1 2 3"[..]);
+//! input! { reader => n: usize, a: [u32; n] }
+//! assert_eq!(a, vec![1, 2, 3]);
+//! ```
+
+pub use crate::{input, input_into, read_one, samples, scan, str_enum};
+
+pub use crate::{Adj, AdjIds, ByteQueue, CaseReader, Dropped, EdgeFilter, FormattedRead, Whitespace};
+pub use crate::{Answers, FormattedWrite, InputWriter, PutInt, Rng, ToInput};
+pub use crate::{FromAscii, FromLeBytes, FromTokens, NegativeUnsigned, NumberFormat, ParseError, SaturatingInt};
+pub use crate::{Base64, BitSet, Byte, Compressed, Date, Dir, DirSet, Duplicates, Either, Hex, Hms, Matrix, Point2, Point3, Poly, Roman, Seg, SmallWord, StringTable, WordHash};
+pub use crate::{SkipComments, DIRS4, DIRS8};
+pub use crate::{assert_round_trip, cycle_nodes, deltas, in_degrees, expand_runs, install_panic_hook, prefix_sums, is_local, run_with_stack};
+#[cfg(feature = "expr")]
+pub use crate::Expr;
@@ -0,0 +1,72 @@
+//! An in-memory source that is filled while reading, e.g. from JavaScript
+//! when the solution runs in a browser.
+
+use std::collections::VecDeque;
+use std::io::{self, BufRead, Read};
+
+/// A source of bytes pushed by the program itself, for environments
+/// without stdin such as WASM. Reach it through `FormattedRead::get_mut`
+/// to push more input. When the queue is empty the reader sees the end of
+/// the input, so push whole lines, or at least whole words followed by a
+/// delimiter, before reading them.
+#[derive(Clone, Debug, Default)]
+pub struct ByteQueue {
+    buf: VecDeque<u8>,
+}
+
+impl ByteQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append `bytes` to the input.
+    pub fn push(&mut self, bytes: &[u8]) {
+        self.buf.extend(bytes);
+    }
+
+    /// Append a string to the input.
+    pub fn push_str(&mut self, s: &str) {
+        self.push(s.as_bytes());
+    }
+
+    /// The number of bytes pushed but not read yet.
+    pub fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+}
+
+impl Read for ByteQueue {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        self.buf.read(out)
+    }
+}
+
+impl BufRead for ByteQueue {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        // the first slice is only empty if the queue is
+        Ok(self.buf.as_slices().0)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.buf.drain(..amt);
+    }
+}
+
+#[test]
+fn test_byte_queue() {
+    use crate::FormattedRead;
+
+    let mut reader = FormattedRead::new(ByteQueue::new());
+    reader.get_mut().push(b"2 ");
+    reader.get_mut().push_str("10 20\n");
+    input! { reader => n: usize, a: [u32; n] }
+    assert_eq!(a, vec![10, 20]);
+    assert!(!reader.has_next().unwrap());
+    reader.get_mut().push(b"x\n");
+    assert_eq!(reader.read_word::<char>().unwrap(), 'x');
+    assert!(reader.get_ref().is_empty());
+}
@@ -0,0 +1,78 @@
+//! Keeping a copy of the raw input, e.g. to feed it to a second solver.
+
+use std::io::{self, BufRead, Read, Write};
+
+/// A source that copies every byte it receives from `inner` into a sink,
+/// as soon as it reaches the buffer. Unlike the case capture, which
+/// records consumed bytes, this includes input that was read ahead but
+/// not parsed yet. Access the copy through `FormattedRead::get_ref`.
+pub struct RawCapture<R, W = Vec<u8>> {
+    inner: R,
+    sink: W,
+    /// How much of the buffer of `inner` has been copied already.
+    copied: usize,
+}
+
+impl<R: BufRead> RawCapture<R> {
+    /// Copy the input into a `Vec<u8>`.
+    pub fn new(inner: R) -> Self {
+        Self::with_sink(inner, vec![])
+    }
+}
+
+impl<R: BufRead, W: Write> RawCapture<R, W> {
+    /// Copy the input into `sink`, e.g. a file.
+    pub fn with_sink(inner: R, sink: W) -> Self {
+        RawCapture { inner, sink, copied: 0 }
+    }
+
+    /// The bytes copied so far.
+    pub fn sink(&self) -> &W {
+        &self.sink
+    }
+
+    pub fn into_parts(self) -> (R, W) {
+        (self.inner, self.sink)
+    }
+}
+
+impl<R: BufRead, W: Write> Read for RawCapture<R, W> {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        let n = self.fill_buf()?.read(out)?;
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<R: BufRead, W: Write> BufRead for RawCapture<R, W> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        let buf = self.inner.fill_buf()?;
+        if buf.len() > self.copied {
+            self.sink.write_all(&buf[self.copied..])?;
+            self.copied = buf.len();
+        }
+        Ok(buf)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt);
+        self.copied = self.copied.saturating_sub(amt);
+    }
+}
+
+#[test]
+fn test_raw_capture() {
+    use crate::FormattedRead;
+
+    let input = b"2 10 20\n3 4 5\n";
+    let source = RawCapture::new(io::BufReader::with_capacity(4, &input[..]));
+    let mut reader = FormattedRead::new(source);
+    input! { reader => n: usize, a: [u32; n] }
+    assert_eq!(a, vec![10, 20]);
+    // the read-ahead after `20` is included
+    assert_eq!(reader.get_ref().sink(), b"2 10 20\n");
+
+    let mut reader = FormattedRead::new(RawCapture::new(&input[..]));
+    assert_eq!(reader.read_word::<u8>().unwrap(), 2);
+    assert_eq!(reader.get_ref().sink(), &input[..]);
+}
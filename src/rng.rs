@@ -0,0 +1,109 @@
+//! Small deterministic random number generator for generated tests.
+
+use std::ops::{Bound, RangeBounds};
+
+/// xorshift64* generator. Not suitable for anything but test data.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// Seed from the first command line argument, so that a failing
+    /// generated test can be repeated with `./gen <seed>`. Without an
+    /// argument the seed is taken from the clock and printed to stderr.
+    pub fn from_args() -> Self {
+        let seed = match std::env::args().nth(1) {
+            Some(arg) => arg.parse().expect("seed must be an integer"),
+            None => {
+                let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH);
+                let seed = now.map_or(0, |d| d.as_nanos() as u64);
+                eprintln!("seed: {}", seed);
+                seed
+            }
+        };
+        Rng::new(seed)
+    }
+
+    /// A uniformly distributed integer in `0..n`, or any `u64` if `n` is 0,
+    /// by Lemire's multiply and reject method.
+    fn below(&mut self, n: u64) -> u64 {
+        if n == 0 {
+            return self.next_u64();
+        }
+        let threshold = n.wrapping_neg() % n;
+        loop {
+            let m = self.next_u64() as u128 * n as u128;
+            if m as u64 >= threshold {
+                return (m >> 64) as u64;
+            }
+        }
+    }
+
+    /// A uniformly distributed integer in `lo..=hi`.
+    pub fn range(&mut self, lo: i64, hi: i64) -> i64 {
+        assert!(lo <= hi, "empty range");
+        // a span of 2^64 wraps to 0, which `below` takes as the full range
+        let span = hi.wrapping_sub(lo).wrapping_add(1) as u64;
+        lo.wrapping_add(self.below(span) as i64)
+    }
+
+    /// Like `range`, but taking a range expression such as `1..=n` or
+    /// `0..n`.
+    pub fn gen_range<B: RangeBounds<i64>>(&mut self, range: B) -> i64 {
+        let lo = match range.start_bound() {
+            Bound::Included(&lo) => lo,
+            Bound::Excluded(&lo) => lo.checked_add(1).expect("empty range"),
+            Bound::Unbounded => i64::MIN,
+        };
+        let hi = match range.end_bound() {
+            Bound::Included(&hi) => hi,
+            Bound::Excluded(&hi) => hi.checked_sub(1).expect("empty range"),
+            Bound::Unbounded => i64::MAX,
+        };
+        self.range(lo, hi)
+    }
+
+    /// A uniformly distributed index in `0..n`.
+    pub fn index(&mut self, n: usize) -> usize {
+        assert!(n > 0, "empty range");
+        self.below(n as u64) as usize
+    }
+
+    /// Shuffle `items` uniformly (Fisher-Yates).
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(i + 1));
+        }
+    }
+}
+
+#[test]
+fn test_rng() {
+    let mut a = Rng::new(5);
+    let mut b = Rng::new(5);
+    for _ in 0..1000 {
+        let x = a.range(-3, 3);
+        assert_eq!(x, b.range(-3, 3));
+        assert!((-3..=3).contains(&x));
+    }
+    assert_ne!(Rng::new(1).next_u64(), Rng::new(2).next_u64());
+    let mut r = Rng::new(0);
+    let _ = r.range(i64::MIN, i64::MAX);
+    let _ = r.gen_range(..);
+    for _ in 0..1000 {
+        assert!((0..3).contains(&r.gen_range(0..3)));
+        assert!((1..=3).contains(&r.gen_range(1..=3)));
+        assert!(r.index(3) < 3);
+    }
+    assert_eq!(r.gen_range(7..8), 7);
+}
@@ -0,0 +1,71 @@
+//! Roman numerals.
+
+use std::fmt;
+
+use crate::FromAscii;
+
+/// A number from 1 to 3999 written as a Roman numeral in canonical form,
+/// e.g. `MCMXCIV`. Formatting it with `Display` writes the numeral.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Roman(pub u32);
+
+const NUMERALS: [(u32, &str); 13] = [
+    (1000, "M"), (900, "CM"), (500, "D"), (400, "CD"),
+    (100, "C"), (90, "XC"), (50, "L"), (40, "XL"),
+    (10, "X"), (9, "IX"), (5, "V"), (4, "IV"), (1, "I"),
+];
+
+impl fmt::Display for Roman {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut n = self.0;
+        for &(value, numeral) in &NUMERALS {
+            while n >= value {
+                f.write_str(numeral)?;
+                n -= value;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromAscii for Roman {
+    fn from_ascii(src: &[u8]) -> Option<Roman> {
+        let mut rest = src;
+        let mut n = 0;
+        for &(value, numeral) in &NUMERALS {
+            while rest.starts_with(numeral.as_bytes()) {
+                n += value;
+                rest = &rest[numeral.len()..];
+            }
+        }
+        // rejects forms like IIII or VX
+        if !rest.is_empty() || n == 0 || n > 3999 || Roman(n).to_string().as_bytes() != src {
+            return None;
+        }
+        Some(Roman(n))
+    }
+}
+
+#[test]
+fn test_roman() {
+    let input = b"MCMXCIV MMMCMXCIX\n";
+    let mut reader = crate::FormattedRead::new(std::io::Cursor::new(&input[..]));
+
+    input! {
+        reader =>
+            a: roman,
+            b: Roman,
+    }
+
+    assert_eq!(a, 1994);
+    assert_eq!(b, Roman(3999));
+    assert_eq!(Roman(1994).to_string(), "MCMXCIV");
+    assert_eq!(Roman(4).to_string(), "IV");
+
+    for bad in &["", "iv", "IIII", "VV", "IC", "XM", "CMCD", "MMMM", "IVI"] {
+        assert_eq!(Roman::from_ascii(bad.as_bytes()), None);
+    }
+    for n in 1..4000 {
+        assert_eq!(Roman::from_ascii(Roman(n).to_string().as_bytes()), Some(Roman(n)));
+    }
+}
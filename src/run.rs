@@ -0,0 +1,29 @@
+//! Running the solution on a thread with a large stack.
+
+use std::thread;
+
+/// Run `f` on a new thread with a stack of `size` bytes and return its
+/// result, e.g. `run_with_stack(64 * 1024 * 1024, solve)` for deeply
+/// recursive solutions. A panic in `f` is propagated to the caller.
+pub fn run_with_stack<T, F>(size: usize, f: F) -> T
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let handle = thread::Builder::new()
+        .stack_size(size)
+        .spawn(f)
+        .expect("failed to spawn thread");
+    match handle.join() {
+        Ok(res) => res,
+        Err(e) => std::panic::resume_unwind(e),
+    }
+}
+
+#[test]
+fn test_run_with_stack() {
+    fn depth(n: u64) -> u64 {
+        if n == 0 { 0 } else { 1 + depth(n - 1) }
+    }
+    assert_eq!(run_with_stack(256 * 1024 * 1024, || depth(200_000)), 200_000);
+}
@@ -0,0 +1,180 @@
+//! Running a solution on sample inputs and checking its output.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use crate::{judge_command, FormattedRead, FormattedWrite, Limits, Outcome, Verdict};
+
+/// Whether `actual` consists of the same whitespace separated tokens as
+/// `expected`, ignoring the amount and kind of whitespace between them.
+pub fn tokens_match(expected: &[u8], actual: &[u8]) -> bool {
+    let tokens = |s: &[u8]| {
+        s.split(|c| c.is_ascii_whitespace())
+            .filter(|t| !t.is_empty())
+            .map(<[u8]>::to_vec)
+            .collect::<Vec<_>>()
+    };
+    tokens(expected) == tokens(actual)
+}
+
+/// The output of `solve` on `input`.
+pub(crate) fn run_solution<F>(input: &[u8], solve: &mut F) -> Vec<u8>
+where
+    F: FnMut(&mut FormattedRead<&[u8]>, &mut FormattedWrite<&mut Vec<u8>>),
+{
+    let mut output = vec![];
+    {
+        let mut reader = FormattedRead::new(input);
+        let mut writer = FormattedWrite::new(&mut output);
+        solve(&mut reader, &mut writer);
+    }
+    output
+}
+
+/// Judge every `<name>.in` in `dir` against `<name>.out` with `judge`.
+/// Panics listing all samples that are not accepted, or if there are none.
+fn check_samples<J>(dir: &Path, mut judge: J)
+where
+    J: FnMut(&[u8], &[u8]) -> Outcome,
+{
+    let mut inputs: Vec<_> = fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("cannot read sample directory {}: {}", dir.display(), e))
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "in"))
+        .collect();
+    inputs.sort();
+    assert!(!inputs.is_empty(), "no samples in {}", dir.display());
+
+    let mut failures = vec![];
+    for input_path in &inputs {
+        let input = fs::read(input_path).expect("failed to read sample input");
+        let expected = fs::read(input_path.with_extension("out"))
+            .unwrap_or_else(|_| panic!("missing output for {}", input_path.display()));
+        let outcome = judge(&input, &expected);
+        if outcome.verdict != Verdict::Accepted {
+            failures.push(format!(
+                "{}: {} in {} ms\nexpected:\n{}\nactual:\n{}",
+                input_path.display(),
+                outcome.verdict,
+                outcome.time.as_millis(),
+                String::from_utf8_lossy(&expected),
+                String::from_utf8_lossy(&outcome.output),
+            ));
+        }
+    }
+    if !failures.is_empty() {
+        panic!("{} of {} samples failed\n{}", failures.len(), inputs.len(), failures.join("\n"));
+    }
+}
+
+/// Run `solve` on every `<name>.in` in `dir` and compare its output with
+/// `<name>.out` using `tokens_match`. Panics listing all failing samples,
+/// or if there are none.
+pub fn run_samples<F>(dir: impl AsRef<Path>, solve: F)
+where
+    F: FnMut(&mut FormattedRead<&[u8]>, &mut FormattedWrite<&mut Vec<u8>>),
+{
+    run_samples_timed(dir, Duration::MAX, solve);
+}
+
+/// Like `run_samples`, but a sample also fails with a time limit verdict
+/// when `solve` takes longer than `time` on it. The solution runs in this
+/// process and is not interrupted, see `run_samples_command` for that.
+pub fn run_samples_timed<F>(dir: impl AsRef<Path>, time: Duration, mut solve: F)
+where
+    F: FnMut(&mut FormattedRead<&[u8]>, &mut FormattedWrite<&mut Vec<u8>>),
+{
+    check_samples(dir.as_ref(), |input, expected| {
+        let start = Instant::now();
+        let output = run_solution(input, &mut solve);
+        let elapsed = start.elapsed();
+        let verdict = if elapsed > time {
+            Verdict::TimeLimit
+        } else if !tokens_match(expected, &output) {
+            Verdict::WrongAnswer
+        } else {
+            Verdict::Accepted
+        };
+        Outcome { verdict, time: elapsed, output }
+    });
+}
+
+/// Like `run_samples`, but run the compiled solution `cmd` on each sample
+/// with `judge_command`, so that it is killed when it exceeds the limits.
+pub fn run_samples_command(dir: impl AsRef<Path>, cmd: &mut Command, limits: &Limits) {
+    check_samples(dir.as_ref(), |input, expected| {
+        judge_command(cmd, input, expected, limits).expect("failed to run the solution")
+    });
+}
+
+/// Define a test running `solve` on the samples in `tests/samples` of the
+/// package (or the given directory), see `run_samples`:
+///
+/// ```rust,ignore
+/// fn solve<R: BufRead, W: Write>(reader: &mut FormattedRead<R>, out: &mut W) { ... }
+///
+/// #[cfg(test)]
+/// mod tests {
+///     comp_input::samples!(super::solve);
+/// }
+/// ```
+#[macro_export]
+macro_rules! samples {
+    ($solve:path) => {
+        $crate::samples!($solve, "tests/samples");
+    };
+    ($solve:path, $dir:expr) => {
+        #[test]
+        fn samples() {
+            let dir = ::std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join($dir);
+            $crate::run_samples(dir, |r, w| $solve(r, w));
+        }
+    };
+}
+
+#[cfg(test)]
+fn sum_solution<R: std::io::BufRead, W: std::io::Write>(reader: &mut FormattedRead<R>, out: &mut W) {
+    input! { reader => n: usize, a: [i64; n] }
+    writeln!(out, "{}", a.iter().sum::<i64>()).unwrap();
+}
+
+#[test]
+fn test_samples() {
+    assert!(tokens_match(b"1 2\n3\n", b"1  2 3"));
+    assert!(!tokens_match(b"1 2", b"12"));
+
+    let dir = std::env::temp_dir().join(format!("comp_input_samples_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("1.in"), "3\n1 2 3\n").unwrap();
+    fs::write(dir.join("1.out"), "6\n").unwrap();
+    fs::write(dir.join("2.in"), "1 -5").unwrap();
+    fs::write(dir.join("2.out"), "-5").unwrap();
+    run_samples(&dir, |r, w| sum_solution(r, w));
+
+    fs::write(dir.join("2.out"), "5").unwrap();
+    let res = std::panic::catch_unwind(|| run_samples(&dir, |r, w| sum_solution(r, w)));
+    assert!(res.is_err());
+
+    fs::write(dir.join("2.out"), "-5").unwrap();
+    let slow = |r: &mut FormattedRead<&[u8]>, w: &mut FormattedWrite<&mut Vec<u8>>| {
+        std::thread::sleep(Duration::from_millis(50));
+        sum_solution(r, w)
+    };
+    let res = std::panic::catch_unwind(|| run_samples_timed(&dir, Duration::from_millis(10), slow));
+    let msg = res.unwrap_err();
+    assert!(msg.downcast_ref::<String>().unwrap().contains("2 of 2 samples failed"));
+    assert!(msg.downcast_ref::<String>().unwrap().contains("time limit exceeded"));
+    #[cfg(unix)]
+    {
+        // the second sample has no line with the numbers
+        let res = std::panic::catch_unwind(|| {
+            let mut cmd = Command::new("sh");
+            cmd.arg("-c").arg("read n; read a b c; echo $((a + b + c))");
+            run_samples_command(&dir, &mut cmd, &Limits::new(Duration::from_secs(5)))
+        });
+        assert!(res.unwrap_err().downcast_ref::<String>().unwrap().contains("1 of 2 samples failed"));
+    }
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,108 @@
+//! `scanf`-like reading of values embedded in literal text.
+
+use std::io::{self, BufRead};
+
+use crate::{consume_ws, FormattedRead, FromAscii};
+
+/// Matches input against a format string, see `FormattedRead::scan`.
+pub struct Scan<'a, R: BufRead> {
+    r: &'a mut FormattedRead<R>,
+    fmt: &'a [u8],
+}
+
+impl<'a, R: BufRead> Scan<'a, R> {
+    fn skip_ws(&mut self) -> io::Result<()> {
+        match consume_ws(&mut self.r.r) {
+            Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof => Ok(()),
+            res => res,
+        }
+    }
+
+    /// Match the format up to the next `{}` (or its end).
+    fn literal(&mut self) -> io::Result<()> {
+        while let Some(&c) = self.fmt.first() {
+            if self.fmt.starts_with(b"{}") {
+                break;
+            }
+            self.fmt = &self.fmt[1..];
+            if c.is_ascii_whitespace() {
+                self.skip_ws()?;
+            } else {
+                match self.r.next_byte()? {
+                    Some(x) if x == c => {}
+                    Some(_) => return Err(io::ErrorKind::InvalidData.into()),
+                    None => return Err(io::ErrorKind::UnexpectedEof.into()),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Read the value for the next `{}` in the format.
+    ///
+    /// The value extends up to the next whitespace or the first byte of the
+    /// literal text following the `{}`, whichever comes first.
+    pub fn value<T: FromAscii>(&mut self) -> io::Result<T> {
+        self.literal()?;
+        if !self.fmt.starts_with(b"{}") {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "more values than {} in format"));
+        }
+        self.fmt = &self.fmt[2..];
+        let stop = self.fmt.first().copied().filter(|c| !c.is_ascii_whitespace() && *c != b'{');
+
+        self.skip_ws()?;
+        self.r.buf.clear();
+        while let Some(c) = self.r.peek_byte()? {
+            if c.is_ascii_whitespace() || Some(c) == stop {
+                break;
+            }
+            self.r.buf.push(c);
+            self.r.r.consume(1);
+        }
+        if self.r.buf.is_empty() {
+            return Err(io::ErrorKind::InvalidData.into());
+        }
+        T::from_ascii_fmt(&self.r.buf, &self.r.numbers).ok_or_else(|| io::ErrorKind::InvalidData.into())
+    }
+
+    /// Match the remaining literal text of the format.
+    pub fn finish(mut self) -> io::Result<()> {
+        self.literal()?;
+        if !self.fmt.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "fewer values than {} in format"));
+        }
+        Ok(())
+    }
+}
+
+impl<R: BufRead> FormattedRead<R> {
+    /// Start matching the input against `fmt`, in which every `{}` stands
+    /// for a value and all other text has to appear literally in the input.
+    /// Whitespace in the format matches any amount of whitespace, and
+    /// leading whitespace in the input is skipped as in `read_word`.
+    ///
+    /// Usually used through the `scan!` macro.
+    pub fn scan<'a>(&'a mut self, fmt: &'a str) -> Scan<'a, R> {
+        let mut scan = Scan { r: self, fmt: fmt.as_bytes() };
+        // errors show up when matching the first literal byte
+        let _ = scan.skip_ws();
+        scan
+    }
+}
+
+#[test]
+fn test_scan() {
+    let input = b"Case #3: 17\n12:34:56\n[-1,2]";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+
+    scan!(reader, "Case #{}: {}", case: usize, x: i64);
+    scan!(reader, "{}:{}:{}", h: u32, m: u32, s: u32);
+    scan!(reader, "[{},{}]", a: i32, b: i32);
+
+    assert_eq!((case, x), (3, 17));
+    assert_eq!((h, m, s), (12, 34, 56));
+    assert_eq!((a, b), (-1, 2));
+
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&b"Case 3"[..]));
+    assert!(reader.scan("Case #{}").value::<u32>().is_err());
+}
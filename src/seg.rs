@@ -0,0 +1,78 @@
+//! Intervals of indices.
+
+use std::io::{self, BufRead};
+use std::ops::Range;
+
+use crate::{FormattedRead, FromTokens};
+
+/// The half-open interval `[l, r)` of 0-based indices.
+///
+/// Reading a `Seg` takes `l r` as they are. The `seg` fragment instead
+/// reads the usual 1-based inclusive `l r` and converts it, `seg(0)`
+/// reads a 0-based inclusive one.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Seg {
+    pub l: usize,
+    pub r: usize,
+}
+
+impl Seg {
+    pub fn new(l: usize, r: usize) -> Self {
+        Seg { l, r }
+    }
+
+    pub fn len(self) -> usize {
+        self.r.saturating_sub(self.l)
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.r <= self.l
+    }
+
+    pub fn contains(self, i: usize) -> bool {
+        self.l <= i && i < self.r
+    }
+
+    pub fn range(self) -> Range<usize> {
+        self.l..self.r
+    }
+}
+
+impl From<Seg> for Range<usize> {
+    fn from(s: Seg) -> Self {
+        s.range()
+    }
+}
+
+impl FromTokens for Seg {
+    fn from_tokens<R: BufRead>(r: &mut FormattedRead<R>) -> io::Result<Self> {
+        Ok(Seg::new(r.read_word()?, r.read_word()?))
+    }
+}
+
+impl<R: BufRead> FormattedRead<R> {
+    /// Read an inclusive interval `l r` of indices starting at `first`,
+    /// usually 1, and convert it to a half-open 0-based `Seg`. Fails with
+    /// `InvalidData` unless `first <= l <= r`.
+    pub fn read_seg(&mut self, first: usize) -> io::Result<Seg> {
+        let l: usize = self.read_word()?;
+        let r: usize = self.read_word()?;
+        if l < first || r < l {
+            let msg = format!("invalid interval {} {}", l, r);
+            return Err(io::Error::new(io::ErrorKind::InvalidData, msg));
+        }
+        Ok(Seg::new(l - first, r - first + 1))
+    }
+}
+
+#[test]
+fn test_seg() {
+    let input = b"1 3 2 2 0 4 5 7 3 2";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+    input! { reader => a: [seg; 2], b: seg(0), c: Seg }
+    assert_eq!(a, vec![Seg::new(0, 3), Seg::new(1, 2)]);
+    assert_eq!(a[1].len(), 1);
+    assert_eq!(b.range(), 0..5);
+    assert!(c.contains(6) && !c.contains(7));
+    assert!(reader.read_seg(1).is_err());
+}
@@ -0,0 +1,54 @@
+//! Cumulative sums and differences computed while reading a sequence.
+
+use std::ops::{Add, Sub};
+
+/// The prefix sums of `items`, starting with `T::default()`, so that the sum
+/// of `items[l..r]` is `res[r] - res[l]`.
+pub fn prefix_sums<T, I>(items: I) -> Vec<T>
+where
+    T: Copy + Default + Add<Output = T>,
+    I: IntoIterator<Item = T>,
+{
+    let items = items.into_iter();
+    let mut res = Vec::with_capacity(items.size_hint().0 + 1);
+    let mut acc = T::default();
+    res.push(acc);
+    for x in items {
+        acc = acc + x;
+        res.push(acc);
+    }
+    res
+}
+
+/// The differences `items[i+1] - items[i]` of adjacent items.
+pub fn deltas<T, I>(items: I) -> Vec<T>
+where
+    T: Copy + Sub<Output = T>,
+    I: IntoIterator<Item = T>,
+{
+    let mut items = items.into_iter();
+    let mut res = Vec::with_capacity(items.size_hint().0.saturating_sub(1));
+    if let Some(mut prev) = items.next() {
+        for x in items {
+            res.push(x - prev);
+            prev = x;
+        }
+    }
+    res
+}
+
+#[test]
+fn test_prefix_sum_deltas() {
+    use crate::FormattedRead;
+
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&b"4 3 -1 4 1\n1 4 9 16"[..]));
+    input! {
+        reader =>
+            n: usize,
+            p: prefix_sum([i64; n]),
+            d: deltas([u32; 4]),
+    }
+    assert_eq!(p, vec![0, 3, 2, 6, 7]);
+    assert_eq!(d, vec![3, 5, 7]);
+    assert!(deltas(Vec::<i32>::new()).is_empty());
+}
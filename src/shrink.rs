@@ -0,0 +1,124 @@
+//! Shrinking a failing stress test case.
+
+use crate::{InputWriter, ToInput};
+
+/// Values that can be made smaller, for reducing a failing case to a
+/// minimal one. Implement it for the case type of a generator to shrink
+/// along its structure, e.g. on the Prüfer code of a tree instead of its
+/// edges.
+pub trait Shrink: Sized {
+    /// Smaller candidates for `self`, more promising ones first.
+    fn shrink(&self) -> Vec<Self>;
+}
+
+macro_rules! shrink_int_impl {
+    ($($t:ty)*) => {
+        $(
+            impl Shrink for $t {
+                #[allow(unused_comparisons)]
+                fn shrink(&self) -> Vec<Self> {
+                    let x = *self;
+                    let step = if x > 0 { x - 1 } else if x < 0 { x + 1 } else { x };
+                    let mut out = vec![0, x / 2, step];
+                    out.dedup();
+                    out.retain(|&y| y != x);
+                    out
+                }
+            }
+        )*
+    };
+}
+
+shrink_int_impl!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
+
+/// Removes halves, quarters, ... down to single elements first, then
+/// shrinks the elements one at a time.
+impl<T: Shrink + Clone> Shrink for Vec<T> {
+    fn shrink(&self) -> Vec<Self> {
+        let mut out = vec![];
+        let mut chunk = self.len();
+        while chunk > 0 {
+            for start in (0..self.len()).step_by(chunk) {
+                let mut v = self[..start].to_vec();
+                v.extend_from_slice(&self[(start + chunk).min(self.len())..]);
+                out.push(v);
+            }
+            chunk /= 2;
+        }
+        for (i, x) in self.iter().enumerate() {
+            for y in x.shrink() {
+                let mut v = self.clone();
+                v[i] = y;
+                out.push(v);
+            }
+        }
+        out
+    }
+}
+
+macro_rules! shrink_tuple_impl {
+    ($($t:ident $i:tt),*) => {
+        impl<$($t: Shrink + Clone),*> Shrink for ($($t,)*) {
+            fn shrink(&self) -> Vec<Self> {
+                let mut out = vec![];
+                $(
+                    for y in self.$i.shrink() {
+                        let mut v = self.clone();
+                        v.$i = y;
+                        out.push(v);
+                    }
+                )*
+                out
+            }
+        }
+    };
+}
+
+shrink_tuple_impl!(A 0);
+shrink_tuple_impl!(A 0, B 1);
+shrink_tuple_impl!(A 0, B 1, C 2);
+shrink_tuple_impl!(A 0, B 1, C 2, D 3);
+
+/// An array written with its length before it, as `n` followed by the
+/// elements, so that it stays consistent while shrinking.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WithLen<T>(pub Vec<T>);
+
+impl<T: ToInput> ToInput for WithLen<T> {
+    fn write_input(&self, w: &mut InputWriter) {
+        w.put(&self.0.len()).newline().put(&self.0);
+    }
+}
+
+impl<T: Shrink + Clone> Shrink for WithLen<T> {
+    fn shrink(&self) -> Vec<Self> {
+        self.0.shrink().into_iter().map(WithLen).collect()
+    }
+}
+
+/// Greedily replace `case` by the first of its `shrink` candidates for
+/// which `fails` still holds, until there is none.
+pub fn shrink_failing<T: Shrink, P: FnMut(&T) -> bool>(mut case: T, mut fails: P) -> T {
+    while let Some(smaller) = case.shrink().into_iter().find(|c| fails(c)) {
+        case = smaller;
+    }
+    case
+}
+
+#[test]
+fn test_shrink() {
+    assert_eq!(5u8.shrink(), vec![0, 2, 4]);
+    assert_eq!((-3i32).shrink(), vec![0, -1, -2]);
+    assert!(0i64.shrink().is_empty());
+
+    // an array with a sum of at least 10 from which no element can be
+    // removed or decreased
+    let min = shrink_failing((1..=100).collect::<Vec<i64>>(), |a| a.iter().sum::<i64>() >= 10);
+    assert_eq!(min.iter().sum::<i64>(), 10);
+    assert!(min.len() <= 2);
+
+    let mut w = InputWriter::new();
+    w.put(&WithLen(vec![4, 2])).newline();
+    assert_eq!(w.as_bytes(), b"2\n4 2\n");
+    assert_eq!(shrink_failing(WithLen(vec![3, 7, 1]), |a| a.0.contains(&7)), WithLen(vec![7]));
+}
@@ -0,0 +1,85 @@
+//! Short words stored inline instead of on the heap.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::Deref;
+
+use crate::FromAscii;
+
+/// A word of at most `N` bytes, stored inline, so that reading many short
+/// words does not allocate each of them like `String` does. Reading a
+/// longer word fails. Dereferences to `str`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SmallWord<const N: usize> {
+    len: usize,
+    bytes: [u8; N],
+}
+
+impl<const N: usize> SmallWord<N> {
+    /// `None` if `s` is longer than `N` bytes.
+    pub fn new(s: &str) -> Option<Self> {
+        if s.len() > N {
+            return None;
+        }
+        let mut bytes = [0; N];
+        bytes[..s.len()].copy_from_slice(s.as_bytes());
+        Some(SmallWord { len: s.len(), bytes })
+    }
+
+    pub fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.bytes[..self.len]).expect("SmallWord holds valid UTF-8")
+    }
+}
+
+impl<const N: usize> Deref for SmallWord<N> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> PartialOrd for SmallWord<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> Ord for SmallWord<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.bytes[..self.len].cmp(&other.bytes[..other.len])
+    }
+}
+
+impl<const N: usize> fmt::Debug for SmallWord<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize> fmt::Display for SmallWord<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<const N: usize> FromAscii for SmallWord<N> {
+    #[inline]
+    fn from_ascii(src: &[u8]) -> Option<Self> {
+        SmallWord::new(std::str::from_utf8(src).ok()?)
+    }
+}
+
+#[test]
+fn test_small_word() {
+    use crate::FormattedRead;
+
+    let input = "abc ab été toolongword".as_bytes();
+    let mut reader = FormattedRead::new(std::io::Cursor::new(input));
+    input! { reader => a, b, c: SmallWord<8> }
+    assert_eq!((&*a, &*b, c.as_str()), ("abc", "ab", "été"));
+    assert!(b < a);
+    assert_eq!(format!("{} {:?}", a, b), "abc \"ab\"");
+    assert!(reader.read_word::<SmallWord<8>>().is_err());
+    assert_eq!(reader.read_word::<SmallWord<11>>().unwrap().len(), 11);
+}
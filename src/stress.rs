@@ -0,0 +1,158 @@
+//! Comparing a solution with a brute force one on all small inputs.
+
+use std::ops::RangeInclusive;
+
+use crate::generate::prufer_tree;
+use crate::samples::run_solution;
+use crate::{shrink_failing, tokens_match, Shrink, FormattedRead, FormattedWrite, InputWriter, ToInput};
+
+/// All sequences of `len` digits in `0..base`, in lexicographic order.
+fn odometer(len: usize, base: usize) -> impl Iterator<Item = Vec<usize>> {
+    let mut next = if len == 0 || base > 0 { Some(vec![0; len]) } else { None };
+    std::iter::from_fn(move || {
+        let cur = next.take()?;
+        let mut digits = cur.clone();
+        for i in (0..len).rev() {
+            digits[i] += 1;
+            if digits[i] < base {
+                next = Some(digits);
+                break;
+            }
+            digits[i] = 0;
+        }
+        Some(cur)
+    })
+}
+
+/// Every array of length at most `max_len` with values in `values`,
+/// shorter ones first.
+pub fn all_arrays(max_len: usize, values: RangeInclusive<i64>) -> impl Iterator<Item = Vec<i64>> {
+    let (lo, hi) = values.into_inner();
+    let base = if lo <= hi { (hi - lo + 1) as usize } else { 0 };
+    (0..=max_len).flat_map(move |len| odometer(len, base).map(move |d| d.into_iter().map(|x| lo + x as i64).collect()))
+}
+
+/// Every labeled tree on the nodes `1..=n`, `n^(n-2)` of them, as edge
+/// lists like `Rng::gen_tree` makes them.
+pub fn all_trees(n: usize) -> impl Iterator<Item = Vec<(usize, usize)>> {
+    // with fewer than 2 nodes the only tree has no edges, and so does the
+    // empty code
+    odometer(n.saturating_sub(2), n).map(move |code| if n < 2 { vec![] } else { prufer_tree(&code) })
+}
+
+/// The input for `case` and the outputs of `brute` and `fast` on it, if they
+/// differ.
+fn mismatch<T, B, F>(case: &T, brute: &mut B, fast: &mut F) -> Option<[Vec<u8>; 3]>
+where
+    T: ToInput,
+    B: FnMut(&mut FormattedRead<&[u8]>, &mut FormattedWrite<&mut Vec<u8>>),
+    F: FnMut(&mut FormattedRead<&[u8]>, &mut FormattedWrite<&mut Vec<u8>>),
+{
+    let mut w = InputWriter::new();
+    w.put(case).newline();
+    let expected = run_solution(w.as_bytes(), brute);
+    let actual = run_solution(w.as_bytes(), fast);
+    if tokens_match(&expected, &actual) {
+        None
+    } else {
+        Some([w.into_bytes(), expected, actual])
+    }
+}
+
+fn report(count: usize, [input, expected, actual]: [Vec<u8>; 3]) -> ! {
+    panic!(
+        "case {} differs\ninput:\n{}\nbrute force:\n{}\nsolution:\n{}",
+        count,
+        String::from_utf8_lossy(&input),
+        String::from_utf8_lossy(&expected),
+        String::from_utf8_lossy(&actual),
+    );
+}
+
+/// Write each of `cases` as input and compare the outputs of `brute` and
+/// `fast` on it with `tokens_match`. Panics with the input and both
+/// outputs at the first difference. Returns the number of cases checked.
+pub fn check_all<T, I, B, F>(cases: I, mut brute: B, mut fast: F) -> usize
+where
+    T: ToInput,
+    I: IntoIterator<Item = T>,
+    B: FnMut(&mut FormattedRead<&[u8]>, &mut FormattedWrite<&mut Vec<u8>>),
+    F: FnMut(&mut FormattedRead<&[u8]>, &mut FormattedWrite<&mut Vec<u8>>),
+{
+    let mut count = 0;
+    for case in cases {
+        if let Some(m) = mismatch(&case, &mut brute, &mut fast) {
+            report(count, m);
+        }
+        count += 1;
+    }
+    count
+}
+
+/// Like `check_all`, but first shrink a failing case with `shrink_failing`
+/// and report the smallest one that still fails, which makes large random
+/// cases usable.
+pub fn check_shrinking<T, I, B, F>(cases: I, mut brute: B, mut fast: F) -> usize
+where
+    T: ToInput + Shrink,
+    I: IntoIterator<Item = T>,
+    B: FnMut(&mut FormattedRead<&[u8]>, &mut FormattedWrite<&mut Vec<u8>>),
+    F: FnMut(&mut FormattedRead<&[u8]>, &mut FormattedWrite<&mut Vec<u8>>),
+{
+    let mut count = 0;
+    for case in cases {
+        if mismatch(&case, &mut brute, &mut fast).is_some() {
+            let min = shrink_failing(case, |c| mismatch(c, &mut brute, &mut fast).is_some());
+            report(count, mismatch(&min, &mut brute, &mut fast).unwrap());
+        }
+        count += 1;
+    }
+    count
+}
+
+#[test]
+fn test_check_all() {
+    use std::io::{BufRead, Write};
+
+    assert_eq!(all_arrays(2, 0..=2).count(), 1 + 3 + 9);
+    assert_eq!(all_trees(4).count(), 16);
+    assert_eq!(all_trees(1).collect::<Vec<_>>(), vec![vec![]]);
+
+    // the maximum subarray sum, by brute force and by Kadane's algorithm
+    fn brute<R: BufRead, W: Write>(r: &mut FormattedRead<R>, out: &mut W) {
+        input! { r => n: usize, a: [i64; n] }
+        let best = (0..n).flat_map(|i| (i + 1..=n).map(move |j| (i, j))).map(|(i, j)| a[i..j].iter().sum()).max();
+        writeln!(out, "{}", best.unwrap_or(0i64)).unwrap();
+    }
+    fn kadane<R: BufRead, W: Write>(r: &mut FormattedRead<R>, out: &mut W) {
+        input! { r => n: usize, a: [i64; n] }
+        let (mut best, mut cur) = (if n == 0 { 0 } else { i64::MIN }, 0);
+        for x in a {
+            cur = x.max(cur + x);
+            best = best.max(cur);
+        }
+        writeln!(out, "{}", best).unwrap();
+    }
+    let cases = || all_arrays(4, -2..=2).map(|a| (a.len(), a));
+    assert_eq!(check_all(cases(), |r, w| brute(r, w), |r, w| kadane(r, w)), 781);
+
+    // forgetting that all values may be negative
+    fn wrong<R: BufRead, W: Write>(r: &mut FormattedRead<R>, out: &mut W) {
+        input! { r => n: usize, a: [i64; n] }
+        let (mut best, mut cur) = (0, 0);
+        for x in a {
+            cur = (cur + x).max(0);
+            best = best.max(cur);
+        }
+        writeln!(out, "{}", best).unwrap();
+    }
+    let res = std::panic::catch_unwind(|| check_all(cases(), |r, w| brute(r, w), |r, w| wrong(r, w)));
+    let msg = res.unwrap_err();
+    assert!(msg.downcast_ref::<String>().unwrap().contains("input:\n1 -2\n"));
+
+    let mut rng = crate::Rng::new(3);
+    let big = crate::WithLen((0..1000).map(|_| rng.range(-1000, -1)).collect());
+    let res = std::panic::catch_unwind(|| check_shrinking(Some(big), |r, w| brute(r, w), |r, w| wrong(r, w)));
+    let msg = res.unwrap_err();
+    assert!(msg.downcast_ref::<String>().unwrap().contains("input:\n1\n-1\n"));
+}
@@ -0,0 +1,97 @@
+//! Dense ids for the distinct words of the input.
+
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::io::{self, BufRead};
+
+use crate::FormattedRead;
+
+const NONE: u32 = u32::MAX;
+
+/// Assigns the ids `0, 1, 2, ...` to distinct words in the order they are
+/// first seen. The words are kept in a single buffer, and the table keys
+/// them by hash, so each distinct word costs its bytes and a few integers.
+#[derive(Clone, Debug, Default)]
+pub struct StringTable {
+    bytes: Vec<u8>,
+    ends: Vec<usize>,
+    /// The first id with a hash, further ones are chained through `next`.
+    heads: HashMap<u64, u32>,
+    next: Vec<u32>,
+    hasher: RandomState,
+}
+
+impl StringTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of distinct words.
+    pub fn len(&self) -> usize {
+        self.ends.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ends.is_empty()
+    }
+
+    /// The word with the given id.
+    pub fn word(&self, id: u32) -> &[u8] {
+        let i = id as usize;
+        let start = if i == 0 { 0 } else { self.ends[i - 1] };
+        &self.bytes[start..self.ends[i]]
+    }
+
+    fn find(&self, hash: u64, word: &[u8]) -> Option<u32> {
+        let mut id = *self.heads.get(&hash)?;
+        while id != NONE {
+            if self.word(id) == word {
+                return Some(id);
+            }
+            id = self.next[id as usize];
+        }
+        None
+    }
+
+    /// The id of `word`, if it is in the table.
+    pub fn get(&self, word: &[u8]) -> Option<u32> {
+        self.find(self.hasher.hash_one(word), word)
+    }
+
+    /// The id of `word`, adding it if it is new.
+    pub fn id(&mut self, word: &[u8]) -> u32 {
+        let hash = self.hasher.hash_one(word);
+        if let Some(id) = self.find(hash, word) {
+            return id;
+        }
+        let id = self.ends.len() as u32;
+        assert!(id != NONE, "too many distinct words");
+        self.bytes.extend_from_slice(word);
+        self.ends.push(self.bytes.len());
+        let head = self.heads.entry(hash).or_insert(NONE);
+        self.next.push(*head);
+        *head = id;
+        id
+    }
+}
+
+impl<R: BufRead> FormattedRead<R> {
+    /// Read a word and return its id in `table`, adding it if it is new.
+    pub fn read_id(&mut self, table: &mut StringTable) -> io::Result<u32> {
+        self.read_word_with(|w| Some(table.id(w)))
+    }
+}
+
+#[test]
+fn test_string_table() {
+    let input = b"3 alice bob alice carol bob";
+    let mut reader = FormattedRead::new(io::Cursor::new(&input[..]));
+    let mut names = StringTable::new();
+    input! { reader => n: usize, a: [id(names); n + 2] }
+    assert_eq!(a, vec![0, 1, 0, 2, 1]);
+    assert_eq!(names.len(), 3);
+    assert_eq!(names.word(2), b"carol");
+    assert_eq!(names.get(b"bob"), Some(1));
+    assert_eq!(names.get(b"dave"), None);
+}
@@ -0,0 +1,247 @@
+//! Times and dates.
+
+use std::convert::TryFrom;
+use std::time::Duration;
+
+use crate::FromAscii;
+
+/// Parse a fixed number of decimal digits.
+fn digits(src: &[u8]) -> Option<u32> {
+    let mut res: u32 = 0;
+    for &c in src {
+        res = res.checked_mul(10)?.checked_add((c as char).to_digit(10)?)?;
+    }
+    Some(res)
+}
+
+/// A time of day given as `HH:MM` or `HH:MM:SS`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Hms {
+    pub h: u32,
+    pub m: u32,
+    pub s: u32,
+}
+
+impl Hms {
+    /// Seconds since midnight.
+    pub fn seconds(self) -> u32 {
+        (self.h * 60 + self.m) * 60 + self.s
+    }
+}
+
+impl FromAscii for Hms {
+    fn from_ascii(src: &[u8]) -> Option<Hms> {
+        let mut parts = src.split(|&c| c == b':');
+        let h = parts.next()?;
+        let m = parts.next()?;
+        let s = parts.next();
+        if parts.next().is_some() || h.is_empty() || h.len() > 2 || m.len() != 2 {
+            return None;
+        }
+
+        let res = Hms {
+            h: digits(h)?,
+            m: digits(m)?,
+            s: match s {
+                Some(s) if s.len() == 2 => digits(s)?,
+                Some(_) => return None,
+                None => 0,
+            },
+        };
+        if res.h < 24 && res.m < 60 && res.s < 60 {
+            Some(res)
+        } else {
+            None
+        }
+    }
+}
+
+/// A calendar date in the proleptic Gregorian calendar.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    pub y: i32,
+    pub m: u32,
+    pub d: u32,
+}
+
+fn is_leap(y: i32) -> bool {
+    y % 4 == 0 && (y % 100 != 0 || y % 400 == 0)
+}
+
+fn days_in_month(y: i32, m: u32) -> u32 {
+    match m {
+        2 if is_leap(y) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl Date {
+    /// Returns `None` if `m` or `d` is out of range.
+    pub fn new(y: i32, m: u32, d: u32) -> Option<Date> {
+        if (1..=12).contains(&m) && d >= 1 && d <= days_in_month(y, m) {
+            Some(Date { y, m, d })
+        } else {
+            None
+        }
+    }
+
+    /// Days since 1970-01-01.
+    pub fn day_number(self) -> i64 {
+        let y = i64::from(self.y) - i64::from(self.m <= 2);
+        let era = y.div_euclid(400);
+        let yoe = y - era * 400;
+        let m = i64::from(self.m);
+        let doy = (153 * (m + if m > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(self.d) - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146097 + doe - 719468
+    }
+
+    /// Inverse of `day_number`.
+    pub fn from_day_number(n: i64) -> Date {
+        let z = n + 719468;
+        let era = z.div_euclid(146097);
+        let doe = z - era * 146097;
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let y = yoe + era * 400 + i64::from(m <= 2);
+        Date { y: y as i32, m, d }
+    }
+
+    /// Parse `src` according to `fmt`, in which runs of `Y`, `M` and `D`
+    /// stand for exactly that many digits of the year, month and day, and
+    /// every other byte has to appear literally, e.g. `"DD/MM/YYYY"`.
+    pub fn from_ascii_fmt(src: &[u8], fmt: &str) -> Option<Date> {
+        let fmt = fmt.as_bytes();
+        if src.len() != fmt.len() {
+            return None;
+        }
+        let (mut y, mut m, mut d) = (None, None, None);
+        let mut i = 0;
+        while i < fmt.len() {
+            let c = fmt[i];
+            let len = fmt[i..].iter().take_while(|&&x| x == c).count();
+            let field = match c {
+                b'Y' => &mut y,
+                b'M' => &mut m,
+                b'D' => &mut d,
+                _ => {
+                    if src[i] != c {
+                        return None;
+                    }
+                    i += 1;
+                    continue;
+                }
+            };
+            if field.is_some() {
+                return None;
+            }
+            *field = Some(digits(&src[i..i + len])?);
+            i += len;
+        }
+        Date::new(i32::try_from(y?).ok()?, m?, d?)
+    }
+}
+
+/// Accepts `YYYY-MM-DD` and `DD.MM.YYYY`.
+impl FromAscii for Date {
+    fn from_ascii(src: &[u8]) -> Option<Date> {
+        Date::from_ascii_fmt(src, "YYYY-MM-DD").or_else(|| Date::from_ascii_fmt(src, "DD.MM.YYYY"))
+    }
+}
+
+/// A non-negative integer followed by one of the units `ns`, `us`, `ms`,
+/// `s`, `m`, `h` or `d`, e.g. `500ms`.
+impl FromAscii for Duration {
+    fn from_ascii(src: &[u8]) -> Option<Duration> {
+        let split = src.iter().position(|c| !c.is_ascii_digit())?;
+        if split > 19 {
+            // might overflow
+            return None;
+        }
+        let n = <u64 as FromAscii>::from_ascii(&src[..split])?;
+        let secs = |k: u64| n.checked_mul(k).map(Duration::from_secs);
+        match &src[split..] {
+            b"ns" => Some(Duration::from_nanos(n)),
+            b"us" => Some(Duration::from_micros(n)),
+            b"ms" => Some(Duration::from_millis(n)),
+            b"s" => Some(Duration::from_secs(n)),
+            b"m" => secs(60),
+            b"h" => secs(60 * 60),
+            b"d" => secs(24 * 60 * 60),
+            _ => None,
+        }
+    }
+}
+
+#[test]
+fn test_hms() {
+    let input = b"9:05 23:59:59 00:00\n";
+    let mut reader = crate::FormattedRead::new(std::io::Cursor::new(&input[..]));
+
+    input! {
+        reader =>
+            a: Hms,
+            b, c: time,
+    }
+
+    assert_eq!(a, Hms { h: 9, m: 5, s: 0 });
+    assert_eq!(b, 86399);
+    assert_eq!(c, 0);
+
+    for bad in &["24:00", "12:60", "1:2", "12:30:5", "12:30:00:00", "12-30", ":30"] {
+        assert_eq!(Hms::from_ascii(bad.as_bytes()), None);
+    }
+}
+
+#[test]
+fn test_date() {
+    let input = b"2024-02-29 01.03.2024 31/12/1969\n";
+    let mut reader = crate::FormattedRead::new(std::io::Cursor::new(&input[..]));
+
+    input! {
+        reader =>
+            a, b: Date,
+            c: date("DD/MM/YYYY"),
+    }
+
+    assert_eq!(a, Date { y: 2024, m: 2, d: 29 });
+    assert_eq!(b.day_number() - a.day_number(), 1);
+    assert_eq!(c.day_number(), -1);
+    assert_eq!(Date::from_day_number(0), Date { y: 1970, m: 1, d: 1 });
+    assert_eq!(Date::from_day_number(a.day_number()), a);
+
+    for bad in &["2023-02-29", "2024-13-01", "2024-1-01", "2024/01/01", "1.1.2024", "01.01.2024x"] {
+        assert_eq!(Date::from_ascii(bad.as_bytes()), None);
+    }
+    // too many digits for the year overflow instead of panicking
+    assert_eq!(Date::from_ascii_fmt(b"99999999999-01-01", "YYYYYYYYYYY-MM-DD"), None);
+    assert_eq!(Date::from_ascii_fmt(b"3000000000-01-01", "YYYYYYYYYY-MM-DD"), None);
+}
+
+#[test]
+fn test_duration() {
+    let input = b"500ms 2s 3m 1h 7us\n";
+    let mut reader = crate::FormattedRead::new(std::io::Cursor::new(&input[..]));
+
+    input! {
+        reader =>
+            a: [Duration; 5],
+    }
+
+    assert_eq!(a, vec![
+        Duration::from_millis(500),
+        Duration::from_secs(2),
+        Duration::from_secs(180),
+        Duration::from_secs(3600),
+        Duration::from_micros(7),
+    ]);
+
+    for bad in &["5", "ms", "5 ms", "-5s", "5sec", "1.5s", "99999999999999999999ns"] {
+        assert_eq!(Duration::from_ascii(bad.as_bytes()), None);
+    }
+}
@@ -0,0 +1,144 @@
+//! Panic hook reporting where in the input the reader was.
+
+use std::collections::VecDeque;
+use std::io::{self, BufRead, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::FormattedRead;
+
+const CONTEXT: usize = 64;
+
+/// Position and surroundings of the reader, only kept up to date once a
+/// panic hook has been installed for it.
+#[derive(Default)]
+pub(crate) struct Trace {
+    enabled: AtomicBool,
+    log: Mutex<TraceLog>,
+}
+
+#[derive(Default)]
+struct TraceLog {
+    newlines: u64,
+    col: u64,
+    offset: u64,
+    recent: VecDeque<u8>,
+    upcoming: Vec<u8>,
+}
+
+impl Trace {
+    #[inline]
+    pub(crate) fn enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Remember the start of the buffered input.
+    pub(crate) fn peek(&self, buf: &[u8]) {
+        if let Ok(mut log) = self.log.lock() {
+            log.upcoming.clear();
+            log.upcoming.extend_from_slice(&buf[..buf.len().min(CONTEXT)]);
+        }
+    }
+
+    pub(crate) fn advance(&self, bytes: &[u8]) {
+        let mut log = match self.log.lock() {
+            Ok(log) => log,
+            Err(_) => return,
+        };
+        for &c in bytes {
+            if c == b'\n' {
+                log.newlines += 1;
+                log.col = 0;
+            } else {
+                log.col += 1;
+            }
+        }
+        log.offset += bytes.len() as u64;
+        log.recent.extend(&bytes[bytes.len().saturating_sub(CONTEXT)..]);
+        while log.recent.len() > CONTEXT {
+            log.recent.pop_front();
+        }
+        let n = bytes.len().min(log.upcoming.len());
+        log.upcoming.drain(..n);
+    }
+
+    /// Undo `advance` for `bytes`, the last ones consumed, which were given
+    /// back. The column is only recovered from the recent input, so it may
+    /// be off after a long line.
+    pub(crate) fn retreat(&self, bytes: &[u8]) {
+        let mut log = match self.log.lock() {
+            Ok(log) => log,
+            Err(_) => return,
+        };
+        let newlines = bytes.iter().filter(|&&c| c == b'\n').count() as u64;
+        log.newlines = log.newlines.saturating_sub(newlines);
+        log.offset = log.offset.saturating_sub(bytes.len() as u64);
+        let len = log.recent.len().saturating_sub(bytes.len());
+        log.recent.truncate(len);
+        log.col = if newlines == 0 {
+            log.col.saturating_sub(bytes.len() as u64)
+        } else {
+            log.recent.iter().rev().take_while(|&&c| c != b'\n').count() as u64
+        };
+        let mut upcoming = bytes[..bytes.len().min(CONTEXT)].to_vec();
+        upcoming.extend_from_slice(&log.upcoming);
+        upcoming.truncate(CONTEXT);
+        log.upcoming = upcoming;
+    }
+
+    fn report(&self) -> String {
+        let log = match self.log.try_lock() {
+            Ok(log) => log,
+            Err(_) => return "reader state unavailable".to_owned(),
+        };
+        let recent: Vec<u8> = log.recent.iter().copied().collect();
+        let recent = String::from_utf8_lossy(&recent);
+        let tokens: Vec<&str> = recent.split_ascii_whitespace().collect();
+        let tokens = &tokens[tokens.len().saturating_sub(5)..];
+        format!(
+            "reader at line {}, column {} (byte {})\nlast tokens: {}\nupcoming input: {:?}",
+            log.newlines + 1,
+            log.col + 1,
+            log.offset,
+            tokens.join(" "),
+            String::from_utf8_lossy(&log.upcoming),
+        )
+    }
+}
+
+/// Install a panic hook printing the position of `reader` in the input, the
+/// last few tokens consumed and the unparsed input following them to
+/// stderr, before running the previous hook. The position is only tracked
+/// from the time the hook is installed.
+pub fn install_panic_hook<R: BufRead>(reader: &FormattedRead<R>) {
+    let trace = Arc::clone(&reader.r.trace);
+    trace.enabled.store(true, Ordering::Relaxed);
+    let prev = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = writeln!(io::stderr(), "{}", trace.report());
+        prev(info);
+    }));
+}
+
+#[test]
+fn test_trace() {
+    let input = b"3\n10 20 abc 40\n";
+    let mut reader = FormattedRead::new(io::Cursor::new(&input[..]));
+    reader.r.trace.enabled.store(true, Ordering::Relaxed);
+    input! { reader => n: usize, a, b: u32 }
+    assert_eq!((n, a, b), (3, 10, 20));
+    assert!(reader.read_word::<u32>().is_err());
+    assert_eq!(
+        reader.r.trace.report(),
+        "reader at line 2, column 7 (byte 8)\nlast tokens: 3 10 20\nupcoming input: \"abc 40\\n\"",
+    );
+
+    // the same with the failing word given back from across buffers
+    let mut reader = FormattedRead::new(io::BufReader::with_capacity(2, &input[..]));
+    reader.r.trace.enabled.store(true, Ordering::Relaxed);
+    input! { reader => _n: usize, _a, _b: u32 }
+    assert!(reader.read_word::<u32>().is_err());
+    assert!(reader.r.trace.report().starts_with("reader at line 2, column 7 (byte 8)\nlast tokens: 3 10 20\n"));
+    assert_eq!(reader.read_word::<String>().unwrap(), "abc");
+    assert!(reader.r.trace.report().starts_with("reader at line 2, column 11 (byte 12)\nlast tokens: 3 10 20 abc\n"));
+}
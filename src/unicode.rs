@@ -0,0 +1,124 @@
+//! Tokenization of UTF-8 input with Unicode whitespace.
+
+use std::io::{self, BufRead};
+
+use crate::{FormattedRead, Whitespace};
+
+impl<R: BufRead> FormattedRead<R> {
+    /// If `unicode` is set, `read_word` decodes the input as UTF-8 and also
+    /// separates words at non-ASCII whitespace such as U+00A0 or U+3000.
+    /// Words that are not valid UTF-8 are rejected.
+    pub fn with_unicode(mut self, unicode: bool) -> Self {
+        self.unicode = unicode;
+        self
+    }
+
+    pub(crate) fn read_utf8_char(&mut self) -> io::Result<Option<char>> {
+        let first = match self.next_byte()? {
+            Some(c) => c,
+            None => return Ok(None),
+        };
+        let len = match first {
+            0x00..=0x7f => return Ok(Some(first as char)),
+            0xc0..=0xdf => 2,
+            0xe0..=0xef => 3,
+            0xf0..=0xf7 => 4,
+            _ => return Err(io::ErrorKind::InvalidData.into()),
+        };
+        let mut bytes = [first, 0, 0, 0];
+        for b in &mut bytes[1..len] {
+            *b = self.next_byte()?.ok_or(io::ErrorKind::InvalidData)?;
+        }
+        let s = std::str::from_utf8(&bytes[..len]).map_err(|_| io::ErrorKind::InvalidData)?;
+        Ok(s.chars().next())
+    }
+
+    fn is_unicode_delim(&self, c: char) -> bool {
+        if c.is_ascii() {
+            self.delims.contains(c as u8)
+        } else {
+            c.is_whitespace()
+        }
+    }
+
+    pub(crate) fn read_word_unicode<T, F, E>(&mut self, limit: usize, f: F, err: E) -> io::Result<T>
+    where
+        F: FnOnce(&[u8]) -> Option<T>,
+        E: FnOnce(&[u8]) -> io::Error,
+    {
+        let mut c = loop {
+            match self.read_utf8_char()? {
+                Some(c) if self.is_unicode_delim(c) => {
+                    if self.whitespace != Whitespace::Lenient {
+                        return Err(crate::unexpected_whitespace());
+                    }
+                }
+                Some(c) => break c,
+                None => return Err(io::ErrorKind::UnexpectedEof.into()),
+            }
+        };
+
+        let mut word = std::mem::take(&mut self.buf);
+        word.clear();
+        let delim = loop {
+            let mut bytes = [0; 4];
+            word.extend_from_slice(c.encode_utf8(&mut bytes).as_bytes());
+            if word.len() > limit {
+                self.buf = word;
+                return Err(crate::token_too_long());
+            }
+            match self.read_utf8_char()? {
+                Some(next) if !self.is_unicode_delim(next) => c = next,
+                delim => break delim,
+            }
+        };
+        let ascii_delim = delim.filter(char::is_ascii).map(|c| c as u8);
+        if let Some(d) = ascii_delim {
+            self.whitespace.check_delim(d)?;
+        }
+        let res = match f(&word) {
+            Some(res) => {
+                if let Some(d) = ascii_delim {
+                    self.check_trailing(d)?;
+                }
+                self.end_token(ascii_delim);
+                Ok(res)
+            }
+            None => {
+                let e = err(&word);
+                // give back the word and its delimiter
+                if let Some(d) = delim {
+                    let mut bytes = [0; 4];
+                    word.extend_from_slice(d.encode_utf8(&mut bytes).as_bytes());
+                }
+                self.r.unread(&word);
+                Err(e)
+            }
+        };
+        self.buf = word;
+        res
+    }
+}
+
+#[test]
+fn test_unicode() {
+    let input = "Привет\u{a0}мир ж\u{3000}3 é\n".as_bytes();
+    let mut reader = FormattedRead::new(std::io::Cursor::new(input)).with_unicode(true);
+
+    input! {
+        reader =>
+            a, b: String,
+            c: char,
+            n: u32,
+            d: char,
+    }
+
+    assert_eq!(a, "Привет");
+    assert_eq!(b, "мир");
+    assert_eq!(c, 'ж');
+    assert_eq!(n, 3);
+    assert_eq!(d, 'é');
+
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&b"ab\xff"[..])).with_unicode(true);
+    assert!(reader.read_word::<String>().is_err());
+}
@@ -0,0 +1,83 @@
+//! Polynomial hashes of words, for inputs where only hashes are needed.
+
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::sync::OnceLock;
+
+use crate::{FromAscii, Rng};
+
+/// The Mersenne prime `2^61 - 1`, the modulus of the hashes.
+pub const HASH_MOD: u64 = (1 << 61) - 1;
+
+fn mul_mod(a: u64, b: u64) -> u64 {
+    ((a as u128 * b as u128) % HASH_MOD as u128) as u64
+}
+
+/// The base of the hashes, chosen at random once per run, so that the
+/// input cannot be made to collide on purpose.
+pub fn hash_base() -> u64 {
+    static BASE: OnceLock<u64> = OnceLock::new();
+    *BASE.get_or_init(|| {
+        let seed = RandomState::new().hash_one(0u8);
+        let mut rng = Rng::new(seed);
+        256 + rng.next_u64() % (HASH_MOD - 512)
+    })
+}
+
+/// `hash_base()` to the power `n`, modulo `HASH_MOD`.
+pub fn hash_pow(mut n: u64) -> u64 {
+    let mut res = 1;
+    let mut b = hash_base();
+    while n > 0 {
+        if n & 1 == 1 {
+            res = mul_mod(res, b);
+        }
+        b = mul_mod(b, b);
+        n >>= 1;
+    }
+    res
+}
+
+/// A word read as its length and its hash `s[0] * B^(len-1) + ... +
+/// s[len-1]` modulo `HASH_MOD`, with `B = hash_base()`. The hashes of
+/// prefixes combine like those of a rolling hash, see `concat`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct WordHash {
+    pub hash: u64,
+    pub len: usize,
+}
+
+impl WordHash {
+    pub fn of(s: &[u8]) -> Self {
+        let b = hash_base();
+        let hash = s.iter().fold(0, |h, &c| (mul_mod(h, b) + c as u64) % HASH_MOD);
+        WordHash { hash, len: s.len() }
+    }
+
+    /// The hash of the word followed by `other`.
+    pub fn concat(self, other: Self) -> Self {
+        let hash = (mul_mod(self.hash, hash_pow(other.len as u64)) + other.hash) % HASH_MOD;
+        WordHash { hash, len: self.len + other.len }
+    }
+}
+
+impl FromAscii for WordHash {
+    #[inline]
+    fn from_ascii(src: &[u8]) -> Option<Self> {
+        Some(WordHash::of(src))
+    }
+}
+
+#[test]
+fn test_word_hash() {
+    use crate::FormattedRead;
+
+    let input = b"abc ab c abc";
+    let mut reader = FormattedRead::new(std::io::Cursor::new(&input[..]));
+    input! { reader => a, b, c, d: WordHash }
+    assert_eq!(a, d);
+    assert_eq!(a.len, 3);
+    assert_ne!(a, b);
+    assert_eq!(b.concat(c), a);
+    assert_eq!(WordHash::of(b"").concat(a), a);
+}
@@ -0,0 +1,310 @@
+//! Buffered output with fast paths for integers and strings.
+
+use std::fmt;
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex, TryLockError};
+
+const FLUSH_LEN: usize = 1 << 16;
+
+/// Integers written by `FormattedWrite::put_int` without going through
+/// `fmt`.
+pub trait PutInt: Copy {
+    fn put(self, out: &mut Vec<u8>);
+}
+
+/// The two digits of every number below 100.
+const DIGIT_PAIRS: [u8; 200] = {
+    let mut pairs = [0; 200];
+    let mut i = 0;
+    while i < 100 {
+        pairs[2 * i] = b'0' + (i / 10) as u8;
+        pairs[2 * i + 1] = b'0' + (i % 10) as u8;
+        i += 1;
+    }
+    pairs
+};
+
+fn put_digits(mut n: u64, out: &mut Vec<u8>) {
+    let mut digits = [0; 20];
+    let mut i = digits.len();
+    while n >= 100 {
+        let d = (n % 100) as usize * 2;
+        n /= 100;
+        i -= 2;
+        digits[i..i + 2].copy_from_slice(&DIGIT_PAIRS[d..d + 2]);
+    }
+    if n >= 10 {
+        let d = n as usize * 2;
+        i -= 2;
+        digits[i..i + 2].copy_from_slice(&DIGIT_PAIRS[d..d + 2]);
+    } else {
+        i -= 1;
+        digits[i] = b'0' + n as u8;
+    }
+    out.extend_from_slice(&digits[i..]);
+}
+
+/// Like `put_digits`, only going through the slower `u128` division for
+/// values that do not fit into a `u64`.
+fn put_digits_u128(mut n: u128, out: &mut Vec<u8>) {
+    if n <= u64::MAX as u128 {
+        return put_digits(n as u64, out);
+    }
+    let mut digits = [0; 39];
+    let mut i = digits.len();
+    while n > 0 {
+        i -= 1;
+        digits[i] = b'0' + (n % 10) as u8;
+        n /= 10;
+    }
+    out.extend_from_slice(&digits[i..]);
+}
+
+macro_rules! put_uint_impl {
+    ($put:ident, $u:ty: $($t:ty)*) => {
+        $(
+            impl PutInt for $t {
+                #[inline]
+                fn put(self, out: &mut Vec<u8>) {
+                    $put(self as $u, out);
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! put_int_impl {
+    ($put:ident, $u:ty: $($t:ty)*) => {
+        $(
+            impl PutInt for $t {
+                #[inline]
+                fn put(self, out: &mut Vec<u8>) {
+                    if self < 0 {
+                        out.push(b'-');
+                    }
+                    $put(self.unsigned_abs() as $u, out);
+                }
+            }
+        )*
+    };
+}
+
+put_uint_impl!(put_digits, u64: u8 u16 u32 u64 usize);
+put_uint_impl!(put_digits_u128, u128: u128);
+put_int_impl!(put_digits, u64: i8 i16 i32 i64 isize);
+put_int_impl!(put_digits_u128, u128: i128);
+
+/// Buffered writer for the output of a solution. Besides `write!` and
+/// `writeln!`, which work through `io::Write` and `fmt::Write`, the typed
+/// `put_*` methods append directly to the buffer. The buffer is flushed
+/// when the writer is dropped, which includes unwinding from a panic.
+pub struct FormattedWrite<W: Write> {
+    w: W,
+    buf: Vec<u8>,
+}
+
+impl<W: Write> FormattedWrite<W> {
+    pub fn new(w: W) -> Self {
+        FormattedWrite {
+            w,
+            buf: Vec::with_capacity(FLUSH_LEN),
+        }
+    }
+
+    #[inline]
+    fn maybe_flush(&mut self) -> io::Result<()> {
+        if self.buf.len() >= FLUSH_LEN {
+            self.w.write_all(&self.buf)?;
+            self.buf.clear();
+        }
+        Ok(())
+    }
+
+    pub fn put_int<T: PutInt>(&mut self, n: T) -> io::Result<()> {
+        n.put(&mut self.buf);
+        self.maybe_flush()
+    }
+
+    pub fn put_str(&mut self, s: &str) -> io::Result<()> {
+        self.put_bytes(s.as_bytes())
+    }
+
+    pub fn put_bytes(&mut self, s: &[u8]) -> io::Result<()> {
+        self.buf.extend_from_slice(s);
+        self.maybe_flush()
+    }
+
+    pub fn put_byte(&mut self, c: u8) -> io::Result<()> {
+        self.buf.push(c);
+        self.maybe_flush()
+    }
+
+    /// Write each row as its values separated by spaces, one row per line.
+    pub fn put_rows<T: fmt::Display, Row: AsRef<[T]>>(&mut self, rows: &[Row]) -> io::Result<()> {
+        for row in rows {
+            for (j, x) in row.as_ref().iter().enumerate() {
+                if j > 0 {
+                    self.buf.push(b' ');
+                }
+                write!(self.buf, "{}", x)?;
+            }
+            self.buf.push(b'\n');
+            self.maybe_flush()?;
+        }
+        Ok(())
+    }
+
+    /// Like `put_rows`, but for integers using `put_int`.
+    pub fn put_int_rows<T: PutInt, Row: AsRef<[T]>>(&mut self, rows: &[Row]) -> io::Result<()> {
+        for row in rows {
+            for (j, &x) in row.as_ref().iter().enumerate() {
+                if j > 0 {
+                    self.buf.push(b' ');
+                }
+                x.put(&mut self.buf);
+            }
+            self.buf.push(b'\n');
+            self.maybe_flush()?;
+        }
+        Ok(())
+    }
+
+    /// Write a grid of characters, each row on its own line without
+    /// separators.
+    pub fn put_grid<Row: AsRef<[u8]>>(&mut self, rows: &[Row]) -> io::Result<()> {
+        for row in rows {
+            self.buf.extend_from_slice(row.as_ref());
+            self.buf.push(b'\n');
+            self.maybe_flush()?;
+        }
+        Ok(())
+    }
+
+    /// Flush the output and exit the process with `code`. Use this instead
+    /// of `std::process::exit`, which skips the flush in `Drop`.
+    pub fn exit(&mut self, code: i32) -> ! {
+        let _ = self.flush();
+        std::process::exit(code)
+    }
+
+    /// The bytes written so far that have not been passed on yet.
+    pub fn pending(&self) -> &[u8] {
+        &self.buf
+    }
+}
+
+/// Flush `out` from a panic hook, before the previous hook prints the
+/// message. `Drop` flushes while unwinding, but does not run with
+/// `panic = "abort"` or when the writer is owned by another thread. The hook
+/// only holds a weak reference and skips the flush if the panicking code
+/// holds the lock.
+pub fn install_panic_flush<W: Write + Send + 'static>(out: &Arc<Mutex<FormattedWrite<W>>>) {
+    let out = Arc::downgrade(out);
+    let prev = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if let Some(out) = out.upgrade() {
+            let out = match out.try_lock() {
+                Ok(out) => Some(out),
+                Err(TryLockError::Poisoned(e)) => Some(e.into_inner()),
+                Err(TryLockError::WouldBlock) => None,
+            };
+            if let Some(mut out) = out {
+                let _ = out.flush();
+            }
+        }
+        prev(info);
+    }));
+}
+
+impl<W: Write> Write for FormattedWrite<W> {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        self.put_bytes(data)?;
+        Ok(data.len())
+    }
+
+    fn write_all(&mut self, data: &[u8]) -> io::Result<()> {
+        self.put_bytes(data)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.w.write_all(&self.buf)?;
+        self.buf.clear();
+        self.w.flush()
+    }
+}
+
+impl<W: Write> Drop for FormattedWrite<W> {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+impl<W: Write> fmt::Write for FormattedWrite<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.put_str(s).map_err(|_| fmt::Error)
+    }
+}
+
+#[test]
+fn test_writer() {
+    let mut out = FormattedWrite::new(vec![]);
+    out.put_int(-42i64).unwrap();
+    out.put_byte(b' ').unwrap();
+    out.put_int(0u8).unwrap();
+    out.put_byte(b' ').unwrap();
+    out.put_int(i64::MIN).unwrap();
+    out.put_byte(b' ').unwrap();
+    out.put_int(u128::MAX).unwrap();
+    out.put_str("\n").unwrap();
+    let s = "x";
+    writeln!(out, "{:.2} {}", 1.5, s).unwrap();
+    fmt::Write::write_fmt(&mut out, format_args!("{}", 7)).unwrap();
+    assert!(out.w.is_empty());
+    out.flush().unwrap();
+    assert_eq!(
+        std::str::from_utf8(&out.w).unwrap(),
+        "-42 0 -9223372036854775808 340282366920938463463374607431768211455\n1.50 x\n7",
+    );
+
+    for n in [1, 9, 10, 99, 100, 101, 12345, u32::MAX as u64, u64::MAX] {
+        let mut buf = vec![];
+        n.put(&mut buf);
+        (n as u128 + 1).put(&mut buf);
+        assert_eq!(buf, format!("{}{}", n, n as u128 + 1).into_bytes());
+    }
+}
+
+#[test]
+fn test_rows() {
+    let mut out = FormattedWrite::new(vec![]);
+    out.put_int_rows(&[vec![1, -2], vec![], vec![3]]).unwrap();
+    out.put_rows(&[[0.5, 1.0]]).unwrap();
+    out.put_grid(&[b"#.", b".#"]).unwrap();
+    assert_eq!(out.pending(), b"1 -2\n\n3\n0.5 1\n#.\n.#\n");
+}
+
+#[test]
+fn test_flush_on_drop() {
+    let mut sink = vec![];
+    let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let mut out = FormattedWrite::new(&mut sink);
+        out.put_str("partial").unwrap();
+        panic!("solution failed");
+    }));
+    assert!(res.is_err());
+    assert_eq!(sink, b"partial");
+}
+
+#[test]
+fn test_panic_flush() {
+    let out = Arc::new(Mutex::new(FormattedWrite::new(vec![])));
+    install_panic_flush(&out);
+    out.lock().unwrap().put_str("partial").unwrap();
+    let res = std::panic::catch_unwind(|| {
+        panic!("solution failed");
+    });
+    assert!(res.is_err());
+    let out = out.lock().unwrap();
+    assert!(out.pending().is_empty());
+    assert_eq!(out.w, b"partial");
+}